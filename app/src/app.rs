@@ -1,26 +1,45 @@
+use crate::camera::Camera;
 use crate::gfx::Gfx;
-use crate::ui::types::draw_mode::DrawMode;
+use crate::overlay;
+use crate::ui::types::brush_shape::BrushShape;
 use crate::ui::Ui;
 use lemon_antbox_core::simulation::settings::SimulationSettings;
+use lemon_antbox_core::simulation::Simulation;
+use lemon_antbox_core::threaded::ant_buffer::AntBuffer;
 use lemon_antbox_core::threaded::event::SimulationEvent;
 use lemon_antbox_core::threaded::ThreadedSimulation;
 use std::sync::Arc;
-use winit::event::{Event, MouseButton, WindowEvent};
+use winit::event::{ElementState, Event, MouseButton, MouseScrollDelta, WindowEvent};
 use winit::event_loop::EventLoopWindowTarget;
+use winit::keyboard::{KeyCode, PhysicalKey};
 use winit::window::Window;
 
 pub struct App {
     gfx: Gfx,
     ui: Ui,
     simulation: ThreadedSimulation,
+    camera: Camera,
+    is_panning: bool,
+    is_following: bool,
 }
 
+/// How much a single step of the mouse wheel zooms.
+const ZOOM_STEP: f32 = 1.25;
+/// The zoom applied when starting to follow an ant, unless already zoomed in further.
+const FOLLOW_ZOOM: f32 = 4.0;
+/// From this zoom on, food patches are marked with icons.
+const FOOD_ICON_ZOOM: f32 = 4.0;
+
 impl App {
-    pub fn new(window: Arc<Window>, settings: SimulationSettings) -> Self {
+    pub fn new(window: Arc<Window>, simulation: Simulation) -> Self {
+        let settings = simulation.settings();
         Self {
             gfx: Gfx::new(window, settings.width, settings.height),
             ui: Ui::default(),
-            simulation: ThreadedSimulation::spawn(settings),
+            camera: Camera::new(settings.width, settings.height),
+            is_panning: false,
+            is_following: false,
+            simulation: ThreadedSimulation::spawn_with(simulation),
         }
     }
 
@@ -39,13 +58,39 @@ impl App {
                         self.gfx.resize(size.width, size.height);
                     }
                     WindowEvent::CursorMoved { position, .. } => {
+                        let previous = self.gfx.window_pos_to_buffer_pos(self.ui.cursor_pos());
                         self.ui.on_cursor_moved(position);
+                        if self.is_panning {
+                            let current = self.gfx.window_pos_to_buffer_pos(self.ui.cursor_pos());
+                            self.camera
+                                .pan((current.0 - previous.0, current.1 - previous.1));
+                        }
+                    }
+                    WindowEvent::MouseInput {
+                        state,
+                        button: MouseButton::Middle,
+                        ..
+                    } => {
+                        self.is_panning = state == ElementState::Pressed && !response.consumed;
+                    }
+                    WindowEvent::MouseWheel { delta, .. } if !response.consumed => {
+                        self.on_mouse_wheel(delta);
                     }
-                    WindowEvent::MouseInput { state, button, .. } if !response.consumed => {
+                    // A stroke or drag released over a window still has to end
+                    WindowEvent::MouseInput { state, button, .. }
+                        if state == ElementState::Released || !response.consumed =>
+                    {
                         self.ui.on_mouse_input(state, button);
                     }
-                    WindowEvent::KeyboardInput { event, .. } => {
-                        self.ui.on_keyboard_input(&self.simulation, &event);
+                    WindowEvent::ModifiersChanged(modifiers) => {
+                        self.ui.on_modifiers_changed(modifiers.state());
+                    }
+                    // Keys typed into a text field are not meant for the simulation
+                    WindowEvent::KeyboardInput { event, .. } if !response.consumed => {
+                        if event.physical_key == PhysicalKey::Code(KeyCode::Home) {
+                            self.camera.reset();
+                        }
+                        self.ui.on_keyboard_input(&mut self.simulation, &event);
                     }
                     _ => {}
                 }
@@ -59,24 +104,21 @@ impl App {
     }
 
     fn update(&mut self) {
+        let coords = self.cursor_coords();
+        let shape = self.ui.brush_shape();
+
         if let Some(button) = self.ui.consume_cursor_pressed()
-            && let Some(coords) = self.cursor_coords()
+            && let Some(coords) = coords
         {
             match button {
-                MouseButton::Left => match self.ui.draw_mode() {
-                    DrawMode::Ant => {
-                        self.simulation
-                            .spawn_ant(coords.0, coords.1, self.ui.ant_tribe());
-                    }
-                    DrawMode::Nest => {
-                        self.simulation
-                            .spawn_nest(coords.0, coords.1, self.ui.nest_tribe());
+                MouseButton::Left if self.ui.continue_selection(coords) => {}
+                MouseButton::Left if shape.is_drag() => self.ui.begin_drag(coords),
+                MouseButton::Left => {
+                    if self.ui.continue_stroke() {
+                        self.simulation.begin_stroke();
                     }
-                    DrawMode::Food => {
-                        self.simulation
-                            .spawn_food(coords.0, coords.1, self.ui.food_amount());
-                    }
-                },
+                    self.paint(coords, coords);
+                }
                 MouseButton::Right => {
                     self.simulation.inspect_cell(coords.0, coords.1);
                 }
@@ -84,16 +126,78 @@ impl App {
             }
         }
 
+        if self.ui.consume_cursor_released() == Some(MouseButton::Left) {
+            if let Some(origin) = self.ui.take_selection_origin() {
+                if let Some(coords) = coords {
+                    let region = BrushShape::Rectangle.region(origin, coords, 0);
+                    self.simulation.select_ants(region);
+                }
+            } else {
+                self.release_brush(coords, shape);
+            }
+        }
+
+        self.simulation.update_stats_history();
+
         if let Some(event) = self.simulation.next_event() {
             self.handle_sim_event(event);
         }
     }
 
+    fn release_brush(&mut self, coords: Option<(u16, u16)>, shape: BrushShape) {
+        if self.ui.end_stroke() {
+            self.simulation.end_stroke();
+        }
+
+        if let Some(origin) = self.ui.take_drag_origin()
+            && let Some(coords) = coords
+            && shape.is_drag()
+        {
+            self.paint(origin, coords);
+        }
+    }
+
+    fn paint(&mut self, from: (u16, u16), to: (u16, u16)) {
+        let region = self
+            .ui
+            .brush_shape()
+            .region(from, to, self.ui.brush_radius());
+        self.simulation.edit_region(region, self.ui.brush_edit());
+    }
+
     fn handle_sim_event(&mut self, event: SimulationEvent) {
         match event {
             SimulationEvent::InspectedCell(inspected_cell) => {
                 self.ui.set_inspected_cell(*inspected_cell)
             }
+            SimulationEvent::SelectionStats(stats) => self.ui.set_selection_stats(*stats),
+            SimulationEvent::StatsExportFailed(err) => {
+                eprintln!("Stats export failed: {err}");
+            }
+            SimulationEvent::FrameRecordingFailed(err) => {
+                eprintln!("Frame recording failed: {err}");
+                self.ui.on_frame_recording_stopped();
+            }
+            SimulationEvent::AnimationRecordingFinished(path) => {
+                println!("Saved animation to {}", path.display());
+                self.ui.on_animation_recording_stopped();
+            }
+            SimulationEvent::AnimationRecordingFailed(err) => {
+                eprintln!("Animation recording failed: {err}");
+                self.ui.on_animation_recording_stopped();
+            }
+            SimulationEvent::ReplaySaved(path) => {
+                println!("Saved replay to {}", path.display());
+            }
+            SimulationEvent::ReplayFailed(err) => {
+                eprintln!("Replay failed: {err}");
+            }
+            SimulationEvent::CheckpointFailed(err) => {
+                eprintln!("Checkpoint failed: {err}");
+            }
+            SimulationEvent::RewindUnavailable { step } => {
+                eprintln!("Cannot rewind to step {step}, no checkpoint is that old");
+            }
         }
     }
 
@@ -102,13 +206,64 @@ impl App {
             self.ui.draw(ctx, &mut self.simulation);
         });
 
-        self.simulation.draw(self.gfx.pixels_frame());
+        if let Some(settings) = self.ui.take_new_world_request() {
+            self.recreate_world(settings);
+        }
+
+        let inspected_ant = self.simulation.inspected_ant().clone();
+        self.follow_ant(inspected_ant.as_ref());
+
+        let food_icons = self.ui.show_food_icons()
+            && self.camera.zoom() >= FOOD_ICON_ZOOM
+            && self.simulation.settings().display.show_food;
+        self.simulation.state().set_wants_food_patches(food_icons);
+
+        self.camera
+            .render(self.simulation.frame(), self.gfx.pixels_frame());
+        if food_icons {
+            let frame = self.gfx.pixels_frame();
+            overlay::draw_food_markers(frame, &self.camera, self.simulation.food_patches());
+        }
+        if let Some(buffer) = &inspected_ant {
+            self.ui
+                .ant_overlay()
+                .draw(self.gfx.pixels_frame(), &self.camera, buffer);
+        }
         self.gfx.render();
     }
 
+    fn follow_ant(&mut self, inspected_ant: Option<&AntBuffer>) {
+        let Some(buffer) = inspected_ant.filter(|_| self.ui.follow_inspected_ant()) else {
+            self.is_following = false;
+            return;
+        };
+
+        if !self.is_following {
+            self.camera.set_zoom(self.camera.zoom().max(FOLLOW_ZOOM));
+            self.is_following = true;
+        }
+        self.camera.center_on(buffer.ant.x, buffer.ant.y);
+    }
+
+    fn on_mouse_wheel(&mut self, delta: MouseScrollDelta) {
+        let steps = match delta {
+            MouseScrollDelta::LineDelta(_, y) => y,
+            MouseScrollDelta::PixelDelta(pos) => pos.y as f32 / 50.0,
+        };
+        let anchor = self.gfx.window_pos_to_buffer_pos(self.ui.cursor_pos());
+        self.camera.zoom_at(ZOOM_STEP.powf(steps), anchor);
+    }
+
+    /// Replaces the running simulation with an empty world, keeping the UI as it is.
+    fn recreate_world(&mut self, settings: SimulationSettings) {
+        self.gfx.resize_buffer(settings.width, settings.height);
+        self.camera = Camera::new(settings.width, settings.height);
+        self.simulation = ThreadedSimulation::spawn(settings);
+        self.ui.on_world_recreated(&self.simulation);
+    }
+
     fn cursor_coords(&self) -> Option<(u16, u16)> {
-        self.gfx
-            .window_pos_to_pixel(self.ui.cursor_pos())
-            .map(|(x, y)| (x as u16, y as u16))
+        let pos = self.gfx.window_pos_to_buffer_pos(self.ui.cursor_pos());
+        self.camera.buffer_to_cell(pos)
     }
 }
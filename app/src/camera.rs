@@ -0,0 +1,132 @@
+/// Which part of the world is shown, as a zoomed view into the world's frame.
+///
+/// The pixel buffer always has the size of the world, the visible region is upscaled into it.
+pub struct Camera {
+    world_width: u16,
+    world_height: u16,
+    zoom: f32,
+    x: f32,
+    y: f32,
+}
+
+impl Camera {
+    pub const MAX_ZOOM: f32 = 32.0;
+
+    pub fn new(world_width: u16, world_height: u16) -> Self {
+        Self {
+            world_width,
+            world_height,
+            zoom: 1.0,
+            x: 0.0,
+            y: 0.0,
+        }
+    }
+
+    /// The size of the buffer the camera renders into, which always matches the world.
+    pub fn buffer_size(&self) -> (u16, u16) {
+        (self.world_width, self.world_height)
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Zooms around the center of the view.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        let (width, height) = self.view_size();
+        let (center_x, center_y) = (self.x + width / 2.0, self.y + height / 2.0);
+        self.zoom = zoom.clamp(1.0, Self::MAX_ZOOM);
+        self.center_on(center_x, center_y);
+    }
+
+    /// Centers the view on a world position, as far as the world's edges allow.
+    pub fn center_on(&mut self, x: f32, y: f32) {
+        let (width, height) = self.view_size();
+        self.x = x - width / 2.0;
+        self.y = y - height / 2.0;
+        self.clamp_view();
+    }
+
+    pub fn reset(&mut self) {
+        self.zoom = 1.0;
+        self.x = 0.0;
+        self.y = 0.0;
+    }
+
+    /// Zooms by `factor`, keeping the world point under `anchor` (in buffer pixels) in place.
+    pub fn zoom_at(&mut self, factor: f32, anchor: (f32, f32)) {
+        let (world_x, world_y) = self.buffer_to_world(anchor);
+        self.zoom = (self.zoom * factor).clamp(1.0, Self::MAX_ZOOM);
+        self.x = world_x - anchor.0 / self.zoom;
+        self.y = world_y - anchor.1 / self.zoom;
+        self.clamp_view();
+    }
+
+    /// Moves the view by a distance in buffer pixels, as if dragging the world.
+    pub fn pan(&mut self, delta: (f32, f32)) {
+        self.x -= delta.0 / self.zoom;
+        self.y -= delta.1 / self.zoom;
+        self.clamp_view();
+    }
+
+    pub fn world_to_buffer(&self, pos: (f32, f32)) -> (f32, f32) {
+        ((pos.0 - self.x) * self.zoom, (pos.1 - self.y) * self.zoom)
+    }
+
+    pub fn buffer_to_world(&self, pos: (f32, f32)) -> (f32, f32) {
+        (self.x + pos.0 / self.zoom, self.y + pos.1 / self.zoom)
+    }
+
+    /// The world cell under a buffer pixel, if the pixel is inside the buffer.
+    pub fn buffer_to_cell(&self, pos: (f32, f32)) -> Option<(u16, u16)> {
+        // The buffer has the size of the world
+        let inside = pos.0 >= 0.0
+            && pos.1 >= 0.0
+            && pos.0 < self.world_width as f32
+            && pos.1 < self.world_height as f32;
+        // Sample the pixel's center, like rendering does
+        let (x, y) = self.buffer_to_world((pos.0 + 0.5, pos.1 + 0.5));
+        inside.then_some((x as u16, y as u16))
+    }
+
+    /// Copies the visible part of the world's frame into the pixel buffer.
+    pub fn render(&self, world_frame: &[u8], target: &mut [u8]) {
+        if self.zoom == 1.0 {
+            target.copy_from_slice(world_frame);
+            return;
+        }
+
+        let width = self.world_width as usize;
+        let columns = (0..width)
+            .map(|x| self.world_column(x) * 4)
+            .collect::<Vec<_>>();
+
+        for (y, row) in target.chunks_exact_mut(width * 4).enumerate() {
+            let world_y = (self.y + (y as f32 + 0.5) / self.zoom) as usize;
+            let world_y = world_y.min(self.world_height as usize - 1);
+            let source = &world_frame[world_y * width * 4..(world_y + 1) * width * 4];
+
+            for (pixel, &column) in row.chunks_exact_mut(4).zip(&columns) {
+                pixel.copy_from_slice(&source[column..column + 4]);
+            }
+        }
+    }
+
+    fn world_column(&self, x: usize) -> usize {
+        let world_x = (self.x + (x as f32 + 0.5) / self.zoom) as usize;
+        world_x.min(self.world_width as usize - 1)
+    }
+
+    fn view_size(&self) -> (f32, f32) {
+        (
+            self.world_width as f32 / self.zoom,
+            self.world_height as f32 / self.zoom,
+        )
+    }
+
+    fn clamp_view(&mut self) {
+        let (width, height) = self.view_size();
+        self.x = self.x.clamp(0.0, self.world_width as f32 - width);
+        self.y = self.y.clamp(0.0, self.world_height as f32 - height);
+    }
+}
@@ -72,11 +72,19 @@ impl Gfx {
         }
     }
 
-    pub fn window_pos_to_pixel(&self, pos: (f32, f32)) -> Option<(isize, isize)> {
+    /// The buffer pixel at a window position, which may lie outside of the buffer.
+    pub fn window_pos_to_buffer_pos(&self, pos: (f32, f32)) -> (f32, f32) {
+        match self.pixels.window_pos_to_pixel(pos) {
+            Ok((x, y)) => (x as f32, y as f32),
+            Err((x, y)) => (x as f32, y as f32),
+        }
+    }
+
+    /// Re-creates the pixel buffer for a world of a different size.
+    pub fn resize_buffer(&mut self, width: u16, height: u16) {
         self.pixels
-            .window_pos_to_pixel(pos)
-            .ok()
-            .map(|(x, y)| (x as isize, y as isize))
+            .resize_buffer(width as u32, height as u32)
+            .unwrap();
     }
 
     pub fn resize(&mut self, width: u32, height: u32) {
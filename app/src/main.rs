@@ -1,39 +1,77 @@
 use crate::app::App;
+use crate::presets::{presets_directory, DEFAULT_PRESET};
+use lemon_antbox_core::batch::file::SweepFile;
+use lemon_antbox_core::batch::summary::write_summary_csv;
+use lemon_antbox_core::preset::PresetStore;
+use lemon_antbox_core::scenario::Scenario;
 use lemon_antbox_core::simulation::settings::SimulationSettings;
+use lemon_antbox_core::simulation::Simulation;
+use std::fmt::Display;
 use std::sync::Arc;
 use winit::dpi::LogicalSize;
 use winit::event_loop::{ControlFlow, EventLoop};
 use winit::window::WindowBuilder;
 
 mod app;
+mod camera;
 mod gfx;
+mod overlay;
+mod presets;
 mod ui;
 
-const WIDTH: u16 = 640;
-const HEIGHT: u16 = 360;
+/// Tribes of the world started without any preset.
 const TRIBE_COUNT: u8 = 2;
 
+/// Where `--sweep` writes its summary table unless `--output` says otherwise.
+const DEFAULT_SWEEP_OUTPUT: &str = "sweep.csv";
+
 fn main() {
+    if let Some(path) = arg_value("--sweep") {
+        run_sweep(&path);
+        return;
+    }
+
+    let presets = PresetStore::new(presets_directory());
+    let settings = if let Some(name) = arg_value("--preset") {
+        presets
+            .load(&name)
+            .unwrap_or_else(|err| exit_with_error(&name, err))
+    } else if presets.contains(DEFAULT_PRESET) {
+        presets.load(DEFAULT_PRESET).unwrap_or_else(|err| {
+            eprintln!("{DEFAULT_PRESET}: {err}");
+            default_settings()
+        })
+    } else {
+        default_settings()
+    };
+
+    let simulation = if let Some(path) = arg_value("--scenario") {
+        Scenario::load(&path)
+            .and_then(|scenario| Simulation::from_scenario(&scenario))
+            .unwrap_or_else(|err| exit_with_error(&path, err))
+    } else if let Some(path) = arg_value("--map") {
+        Simulation::from_map_image(&path, settings)
+            .unwrap_or_else(|err| exit_with_error(&path, err))
+    } else {
+        Simulation::new(settings)
+    };
+    let width = simulation.settings().width as u32;
+    let height = simulation.settings().height as u32;
+
     let event_loop = EventLoop::new().unwrap();
     event_loop.set_control_flow(ControlFlow::Poll);
 
     let window = Arc::new(
         WindowBuilder::new()
             .with_title("Lemon Antbox")
-            .with_inner_size(LogicalSize::new(WIDTH * 3, HEIGHT * 3))
-            .with_min_inner_size(LogicalSize::new(WIDTH, HEIGHT))
-            .with_resize_increments(LogicalSize::new(WIDTH, HEIGHT))
+            .with_inner_size(LogicalSize::new(width * 3, height * 3))
+            .with_min_inner_size(LogicalSize::new(width, height))
+            .with_resize_increments(LogicalSize::new(width, height))
             .build(&event_loop)
             .unwrap(),
     );
 
-    let settings = SimulationSettings {
-        width: WIDTH,
-        height: HEIGHT,
-        tribe_count: TRIBE_COUNT,
-        ..Default::default()
-    };
-    let mut app = App::new(window.clone(), settings);
+    let mut app = App::new(window.clone(), simulation);
 
     event_loop
         .run(move |event, target| {
@@ -41,3 +79,35 @@ fn main() {
         })
         .unwrap();
 }
+
+fn default_settings() -> SimulationSettings {
+    SimulationSettings {
+        tribe_count: TRIBE_COUNT,
+        ..Default::default()
+    }
+}
+
+/// Runs a parameter sweep without opening a window and writes its summary table.
+fn run_sweep(path: &str) {
+    let sweep = SweepFile::load(path).unwrap_or_else(|err| exit_with_error(path, err));
+    let output = arg_value("--output").unwrap_or_else(|| DEFAULT_SWEEP_OUTPUT.to_string());
+
+    let summaries = sweep.runner().run();
+    write_summary_csv(&summaries, &output).unwrap_or_else(|err| exit_with_error(&output, err));
+    println!("Wrote {} configurations to {output}", summaries.len());
+}
+
+fn arg_value(name: &str) -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+    }
+    None
+}
+
+fn exit_with_error(path: &str, err: impl Display) -> ! {
+    eprintln!("{path}: {err}");
+    std::process::exit(1);
+}
@@ -0,0 +1,147 @@
+use crate::camera::Camera;
+use lemon_antbox_core::simulation::food::FoodPatch;
+use lemon_antbox_core::threaded::ant_buffer::AntBuffer;
+
+/// A piece of fruit, with `s` for the stem, `o` for the outline and `#` for the fill.
+const FOOD_ICON: [&str; 7] = [
+    "...s...", "...s...", ".ooooo.", "o#####o", "o#####o", "o#####o", ".ooooo.",
+];
+const FOOD_ICON_COLORS: [(u8, [u8; 3]); 3] = [
+    (b's', [120, 80, 40]),
+    (b'o', [20, 60, 20]),
+    (b'#', [170, 255, 120]),
+];
+
+const TRAIL_COLOR: [u8; 3] = [255, 255, 255];
+const SENSOR_COLOR: [u8; 3] = [80, 200, 255];
+
+/// Which parts of the inspected ant's overlay are drawn.
+pub struct AntOverlay {
+    pub sensors: bool,
+    pub trail: bool,
+}
+
+impl AntOverlay {
+    /// Draws on top of a frame already rendered through `camera`, in buffer pixels, so the
+    /// overlay stays thin when zoomed in.
+    pub fn draw(&self, frame: &mut [u8], camera: &Camera, buffer: &AntBuffer) {
+        let (width, _) = camera.buffer_size();
+        let mut canvas = Canvas { frame, width };
+
+        if self.trail {
+            let count = buffer.history.len();
+            for (i, pair) in buffer.history.windows(2).enumerate() {
+                // Older positions fade out
+                let alpha = (40 + 180 * (i + 1) / count.max(1)) as u8;
+                canvas.line(
+                    camera.world_to_buffer((pair[0].x, pair[0].y)),
+                    camera.world_to_buffer((pair[1].x, pair[1].y)),
+                    TRAIL_COLOR,
+                    alpha,
+                );
+            }
+        }
+
+        if self.sensors {
+            let ant = camera.world_to_buffer((buffer.ant.x, buffer.ant.y));
+            let senses = [
+                buffer.senses.left,
+                buffer.senses.forward,
+                buffer.senses.right,
+            ];
+            let strongest = senses.iter().copied().fold(0.0, f32::max);
+
+            for (sensor, sensed) in buffer.sensors.iter().zip(senses) {
+                // Brighter rays sensed more of the pheromone the ant is following
+                let strength = if strongest > 0.0 {
+                    sensed / strongest
+                } else {
+                    0.0
+                };
+                let alpha = (90.0 + 165.0 * strength) as u8;
+                let sensor = camera.world_to_buffer(*sensor);
+                canvas.line(ant, sensor, SENSOR_COLOR, alpha);
+
+                let size = (camera.zoom() / 2.0).max(1.0) as i32;
+                canvas.square(sensor, size, SENSOR_COLOR, 255);
+            }
+        }
+    }
+}
+
+/// Marks the center of every food patch, for zoomed-in views where patches are easy to miss.
+pub fn draw_food_markers(frame: &mut [u8], camera: &Camera, patches: &[FoodPatch]) {
+    let (width, _) = camera.buffer_size();
+    let mut canvas = Canvas { frame, width };
+    let scale = (camera.zoom() / 4.0).max(1.0) as i32;
+    let size = FOOD_ICON.len() as i32 * scale;
+
+    for patch in patches {
+        let (x, y) = camera.world_to_buffer((patch.x, patch.y));
+        let (left, top) = (x as i32 - size / 2, y as i32 - size / 2);
+        for (row, line) in FOOD_ICON.iter().enumerate() {
+            for (column, symbol) in line.bytes().enumerate() {
+                let Some((_, color)) = FOOD_ICON_COLORS.iter().find(|(s, _)| *s == symbol) else {
+                    continue;
+                };
+                let (px, py) = (left + column as i32 * scale, top + row as i32 * scale);
+                canvas.rect((px, py), scale, *color, 255);
+            }
+        }
+    }
+}
+
+struct Canvas<'a> {
+    frame: &'a mut [u8],
+    width: u16,
+}
+
+impl Canvas<'_> {
+    fn height(&self) -> i32 {
+        (self.frame.len() / 4 / self.width as usize) as i32
+    }
+
+    fn blend(&mut self, x: i32, y: i32, color: [u8; 3], alpha: u8) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height() {
+            return;
+        }
+
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        let alpha = alpha as u32;
+        for (channel, value) in self.frame[index..index + 3].iter_mut().zip(color) {
+            *channel = ((value as u32 * alpha + *channel as u32 * (255 - alpha)) / 255) as u8;
+        }
+    }
+
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), color: [u8; 3], alpha: u8) {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let steps = dx.abs().max(dy.abs()).ceil().max(1.0);
+        // Skip the start, so connected segments do not blend the joints twice
+        for i in 1..=steps as i32 {
+            let t = i as f32 / steps;
+            self.blend(
+                (from.0 + dx * t) as i32,
+                (from.1 + dy * t) as i32,
+                color,
+                alpha,
+            );
+        }
+    }
+
+    fn rect(&mut self, top_left: (i32, i32), size: i32, color: [u8; 3], alpha: u8) {
+        for y in top_left.1..top_left.1 + size {
+            for x in top_left.0..top_left.0 + size {
+                self.blend(x, y, color, alpha);
+            }
+        }
+    }
+
+    fn square(&mut self, center: (f32, f32), size: i32, color: [u8; 3], alpha: u8) {
+        let (cx, cy) = (center.0 as i32, center.1 as i32);
+        for y in cy - size / 2..=cy + size / 2 {
+            for x in cx - size / 2..=cx + size / 2 {
+                self.blend(x, y, color, alpha);
+            }
+        }
+    }
+}
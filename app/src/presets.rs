@@ -0,0 +1,12 @@
+use std::path::PathBuf;
+
+/// Loaded on startup when no other preset is given.
+pub const DEFAULT_PRESET: &str = "default";
+
+/// Where the presets of the current user are stored.
+pub fn presets_directory() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_default()
+        .join("lemon-antbox")
+        .join("presets")
+}
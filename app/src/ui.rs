@@ -1,11 +1,15 @@
-use crate::ui::types::draw_mode::DrawMode;
+use crate::overlay::AntOverlay;
+use crate::ui::types::brush_shape::BrushShape;
 use crate::ui::windows::main::{MainWindow, MainWindowState};
 use crate::ui::windows::UiWindow;
+use lemon_antbox_core::simulation::group::AntGroupStats;
+use lemon_antbox_core::simulation::region::RegionEdit;
+use lemon_antbox_core::simulation::settings::SimulationSettings;
 use lemon_antbox_core::threaded::event::InspectedCell;
 use lemon_antbox_core::threaded::ThreadedSimulation;
 use winit::dpi::PhysicalPosition;
 use winit::event::{ElementState, KeyEvent, MouseButton};
-use winit::keyboard::{KeyCode, PhysicalKey};
+use winit::keyboard::{KeyCode, ModifiersState, PhysicalKey};
 
 pub mod types;
 mod widgets;
@@ -15,6 +19,12 @@ mod windows;
 pub struct Ui {
     cursor_pos: (f32, f32),
     cursor_pressed: Option<MouseButton>,
+    cursor_released: Option<MouseButton>,
+    /// Where the current drag of a line or rectangle brush, or of a selection, started.
+    drag_origin: Option<(u16, u16)>,
+    is_selecting: bool,
+    is_stroking: bool,
+    modifiers: ModifiersState,
     main_window: MainWindowState,
 }
 
@@ -32,14 +42,19 @@ impl Ui {
             self.cursor_pressed = Some(button);
         } else {
             self.cursor_pressed = None;
+            self.cursor_released = Some(button);
         }
     }
 
-    pub fn on_keyboard_input(&mut self, sim: &ThreadedSimulation, event: &KeyEvent) {
+    pub fn on_keyboard_input(&mut self, sim: &mut ThreadedSimulation, event: &KeyEvent) {
         let PhysicalKey::Code(code) = event.physical_key else {
             return;
         };
 
+        if event.state == ElementState::Pressed && self.on_shortcut(sim, code) {
+            return;
+        }
+
         match code {
             KeyCode::Escape => {
                 if event.state == ElementState::Pressed {
@@ -55,6 +70,25 @@ impl Ui {
         }
     }
 
+    /// Handles the pressed-only shortcuts, returning whether the key was used.
+    fn on_shortcut(&mut self, sim: &mut ThreadedSimulation, code: KeyCode) -> bool {
+        match code {
+            KeyCode::KeyZ if self.modifiers.control_key() && self.modifiers.shift_key() => {
+                sim.redo()
+            }
+            KeyCode::KeyZ if self.modifiers.control_key() => sim.undo(),
+            KeyCode::KeyY if self.modifiers.control_key() => sim.redo(),
+            KeyCode::Period => sim.step(1),
+            KeyCode::F12 => self.main_window.recording.screenshot(sim),
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn on_modifiers_changed(&mut self, modifiers: ModifiersState) {
+        self.modifiers = modifiers;
+    }
+
     pub fn cursor_pos(&self) -> (f32, f32) {
         self.cursor_pos
     }
@@ -67,6 +101,80 @@ impl Ui {
         self.cursor_pressed.take()
     }
 
+    pub fn consume_cursor_released(&mut self) -> Option<MouseButton> {
+        self.cursor_released.take()
+    }
+
+    /// Remembers the start of a drag, keeping the first position while the button is held.
+    pub fn begin_drag(&mut self, coords: (u16, u16)) {
+        self.drag_origin.get_or_insert(coords);
+    }
+
+    /// Whether a stamped brush stroke is in progress, returning `true` when it just started.
+    pub fn continue_stroke(&mut self) -> bool {
+        !std::mem::replace(&mut self.is_stroking, true)
+    }
+
+    /// Returns `true` if a stroke was in progress.
+    pub fn end_stroke(&mut self) -> bool {
+        std::mem::take(&mut self.is_stroking)
+    }
+
+    pub fn take_drag_origin(&mut self) -> Option<(u16, u16)> {
+        self.drag_origin.take()
+    }
+
+    /// Starts or continues a Shift-drag selection, returning `false` if the press is meant for
+    /// the brush instead.
+    pub fn continue_selection(&mut self, coords: (u16, u16)) -> bool {
+        if !self.is_selecting && !self.modifiers.shift_key() {
+            return false;
+        }
+
+        self.is_selecting = true;
+        self.begin_drag(coords);
+        true
+    }
+
+    /// Where the selection that just ended started, if one was in progress.
+    pub fn take_selection_origin(&mut self) -> Option<(u16, u16)> {
+        if std::mem::take(&mut self.is_selecting) {
+            self.drag_origin.take()
+        } else {
+            None
+        }
+    }
+
+    pub fn on_frame_recording_stopped(&mut self) {
+        self.main_window.recording.is_recording_frames = false;
+    }
+
+    pub fn on_animation_recording_stopped(&mut self) {
+        self.main_window.recording.is_recording_animation = false;
+    }
+
+    pub fn take_new_world_request(&mut self) -> Option<SimulationSettings> {
+        self.main_window.new_world.requested.take()
+    }
+
+    /// Carries the UI state over to a freshly created simulation.
+    pub fn on_world_recreated(&mut self, sim: &ThreadedSimulation) {
+        let max_tribe = sim.state().tribe_count().saturating_sub(1);
+        let draw_settings = &mut self.main_window.draw_settings;
+        draw_settings.ant_tribe = draw_settings.ant_tribe.min(max_tribe);
+        draw_settings.nest_tribe = draw_settings.nest_tribe.min(max_tribe);
+
+        // Recordings belong to the old simulation thread and ended with it
+        self.on_frame_recording_stopped();
+        self.on_animation_recording_stopped();
+
+        sim.set_checkpoint_settings(self.main_window.simulation_settings.checkpoint_settings());
+    }
+
+    pub fn set_selection_stats(&mut self, stats: AntGroupStats) {
+        self.main_window.selection.set_stats(stats);
+    }
+
     pub fn set_inspected_cell(&mut self, inspected_cell: InspectedCell) {
         self.main_window
             .cell_inspector_window_state
@@ -76,19 +184,31 @@ impl Ui {
 
 // Window state helpers
 impl Ui {
-    pub fn draw_mode(&self) -> DrawMode {
-        self.main_window.draw_settings.draw_mode
+    pub fn follow_inspected_ant(&self) -> bool {
+        self.main_window.cell_inspector_window_state.follow
+    }
+
+    pub fn ant_overlay(&self) -> AntOverlay {
+        let state = &self.main_window.cell_inspector_window_state;
+        AntOverlay {
+            sensors: state.show_sensors,
+            trail: state.show_trail,
+        }
+    }
+
+    pub fn show_food_icons(&self) -> bool {
+        self.main_window.display_settings.food_icons
     }
 
-    pub fn ant_tribe(&self) -> u8 {
-        self.main_window.draw_settings.ant_tribe
+    pub fn brush_shape(&self) -> BrushShape {
+        self.main_window.draw_settings.brush_shape
     }
 
-    pub fn nest_tribe(&self) -> u8 {
-        self.main_window.draw_settings.nest_tribe
+    pub fn brush_radius(&self) -> u16 {
+        self.main_window.draw_settings.brush_radius
     }
 
-    pub fn food_amount(&self) -> u8 {
-        self.main_window.draw_settings.food_amount
+    pub fn brush_edit(&self) -> RegionEdit {
+        self.main_window.draw_settings.brush_edit()
     }
 }
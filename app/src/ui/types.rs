@@ -1 +1,2 @@
+pub mod brush_shape;
 pub mod draw_mode;
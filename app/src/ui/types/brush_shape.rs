@@ -0,0 +1,55 @@
+use lemon_antbox_core::simulation::region::Region;
+use std::fmt::Display;
+use strum_macros::EnumIter;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter)]
+pub enum BrushShape {
+    #[default]
+    Circle,
+    Square,
+    Line,
+    Rectangle,
+}
+
+impl BrushShape {
+    /// Line and rectangle brushes span from where the drag started to where it was released,
+    /// the others are stamped at the cursor for as long as the button is held.
+    pub fn is_drag(self) -> bool {
+        matches!(self, Self::Line | Self::Rectangle)
+    }
+
+    pub fn region(self, from: (u16, u16), to: (u16, u16), radius: u16) -> Region {
+        match self {
+            Self::Circle => Region::Circle {
+                x: to.0,
+                y: to.1,
+                radius,
+            },
+            Self::Square => Region::Rectangle {
+                x: to.0.saturating_sub(radius),
+                y: to.1.saturating_sub(radius),
+                width: radius * 2 + 1,
+                height: radius * 2 + 1,
+            },
+            Self::Line => Region::Line {
+                from_x: from.0,
+                from_y: from.1,
+                to_x: to.0,
+                to_y: to.1,
+                radius,
+            },
+            Self::Rectangle => Region::Rectangle {
+                x: from.0.min(to.0),
+                y: from.1.min(to.1),
+                width: from.0.abs_diff(to.0) + 1,
+                height: from.1.abs_diff(to.1) + 1,
+            },
+        }
+    }
+}
+
+impl Display for BrushShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
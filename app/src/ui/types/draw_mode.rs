@@ -7,6 +7,8 @@ pub enum DrawMode {
     Ant,
     Nest,
     Food,
+    Wall,
+    Eraser,
 }
 
 impl Display for DrawMode {
@@ -1,5 +1,8 @@
+pub mod ant_history_plot;
 pub mod enum_select;
+pub mod line_plot;
 pub mod option_enum_select;
 pub mod simulation_ant;
 pub mod simulation_cell;
 pub mod toggle_button;
+pub mod tribe_legend;
@@ -0,0 +1,123 @@
+use crate::ui::widgets::line_plot::{LinePlot, LineSeries};
+use egui::{Color32, Response, Ui, Widget};
+use lemon_antbox_core::simulation::ant::AntMode;
+use lemon_antbox_core::threaded::ant_history::{AntRecord, FoodEvent};
+use std::f32::consts::TAU;
+
+const LEFT_COLOR: Color32 = Color32::from_rgb(230, 120, 80);
+const FORWARD_COLOR: Color32 = Color32::from_rgb(120, 200, 120);
+const RIGHT_COLOR: Color32 = Color32::from_rgb(80, 160, 230);
+const TURN_COLOR: Color32 = Color32::from_rgb(220, 220, 220);
+const PICKED_UP_COLOR: Color32 = Color32::from_rgb(201, 160, 56);
+const DELIVERED_COLOR: Color32 = Color32::from_rgb(200, 80, 200);
+
+/// Plots the senses and turns of the inspected ant over its recorded steps.
+pub struct AntHistoryPlot<'a> {
+    history: &'a [AntRecord],
+}
+
+impl<'a> AntHistoryPlot<'a> {
+    pub fn new(history: &'a [AntRecord]) -> Self {
+        Self { history }
+    }
+
+    fn mode_color(mode: AntMode) -> Color32 {
+        match mode {
+            AntMode::Exploring => Color32::from_rgb(90, 90, 90),
+            AntMode::FoodToHome => PICKED_UP_COLOR,
+            AntMode::SearchingHome => Color32::from_rgb(200, 60, 60),
+        }
+    }
+
+    fn food_markers(&self) -> Vec<(usize, Color32)> {
+        self.history
+            .iter()
+            .enumerate()
+            .filter_map(|(i, record)| match record.food_event? {
+                FoodEvent::PickedUp => Some((i, PICKED_UP_COLOR)),
+                FoodEvent::Delivered => Some((i, DELIVERED_COLOR)),
+            })
+            .collect()
+    }
+
+    fn describe(&self, index: usize) -> String {
+        let Some(record) = self.history.get(index) else {
+            return String::new();
+        };
+
+        let mut text = format!(
+            "Step {}\n{:?}\nSenses ({:.2} | {:.2} | {:.2})\nTurn {:.1}°",
+            record.step,
+            record.mode,
+            record.senses.left,
+            record.senses.forward,
+            record.senses.right,
+            record.turn.to_degrees(),
+        );
+        if let Some(event) = record.food_event {
+            text.push_str(&format!("\n{event:?}"));
+        }
+        text
+    }
+}
+
+impl Widget for AntHistoryPlot<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.vertical(|ui| {
+            if self.history.len() < 2 {
+                ui.small("Run the simulation to record the ant's history.");
+                return;
+            }
+
+            let bands = self
+                .history
+                .iter()
+                .map(|record| Self::mode_color(record.mode))
+                .collect::<Vec<_>>();
+            let values = |value: fn(&AntRecord) -> f32| self.history.iter().map(value).collect();
+
+            ui.label("Senses");
+            LinePlot::new(vec![
+                LineSeries {
+                    name: "Left",
+                    color: LEFT_COLOR,
+                    values: values(|record| record.senses.left),
+                },
+                LineSeries {
+                    name: "Forward",
+                    color: FORWARD_COLOR,
+                    values: values(|record| record.senses.forward),
+                },
+                LineSeries {
+                    name: "Right",
+                    color: RIGHT_COLOR,
+                    values: values(|record| record.senses.right),
+                },
+            ])
+            .markers(self.food_markers())
+            .bands(bands.clone())
+            .hover_text(|index| self.describe(index))
+            .ui(ui);
+
+            ui.label("Turn (°)");
+            LinePlot::new(vec![LineSeries {
+                name: "Turn",
+                color: TURN_COLOR,
+                values: values(|record| record.turn.to_degrees()),
+            }])
+            .symmetric(true)
+            .markers(self.food_markers())
+            .bands(bands)
+            .hover_text(|index| self.describe(index))
+            .ui(ui);
+
+            // An ant stuck circling keeps turning the same way
+            let rotation = self.history.iter().map(|record| record.turn).sum::<f32>() / TAU;
+            ui.small(format!(
+                "Net rotation of {rotation:.1} turns over the last {} steps",
+                self.history.len()
+            ));
+        })
+        .response
+    }
+}
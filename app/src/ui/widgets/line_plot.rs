@@ -0,0 +1,153 @@
+use egui::{Color32, Pos2, Rect, Response, Sense, Stroke, Ui, Vec2, Widget};
+
+pub struct LineSeries<'a> {
+    pub name: &'a str,
+    pub color: Color32,
+    pub values: Vec<f32>,
+}
+
+/// A minimal line chart of evenly spaced samples, drawn with the painter.
+pub struct LinePlot<'a> {
+    series: Vec<LineSeries<'a>>,
+    height: f32,
+    symmetric: bool,
+    markers: Vec<(usize, Color32)>,
+    bands: Vec<Color32>,
+    hover_text: Option<Box<dyn Fn(usize) -> String + 'a>>,
+}
+
+impl<'a> LinePlot<'a> {
+    pub fn new(series: Vec<LineSeries<'a>>) -> Self {
+        Self {
+            series,
+            height: 80.0,
+            symmetric: false,
+            markers: Vec::new(),
+            bands: Vec::new(),
+            hover_text: None,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Keeps zero in the middle of the plot.
+    pub fn symmetric(mut self, symmetric: bool) -> Self {
+        self.symmetric = symmetric;
+        self
+    }
+
+    /// Vertical lines at the given sample indices.
+    pub fn markers(mut self, markers: Vec<(usize, Color32)>) -> Self {
+        self.markers = markers;
+        self
+    }
+
+    /// A colored strip along the bottom, one color per sample.
+    pub fn bands(mut self, bands: Vec<Color32>) -> Self {
+        self.bands = bands;
+        self
+    }
+
+    pub fn hover_text(mut self, hover_text: impl Fn(usize) -> String + 'a) -> Self {
+        self.hover_text = Some(Box::new(hover_text));
+        self
+    }
+
+    fn sample_count(&self) -> usize {
+        self.series
+            .iter()
+            .map(|series| series.values.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn value_range(&self) -> (f32, f32) {
+        let values = self.series.iter().flat_map(|series| &series.values);
+        let (min, max) = values.fold((0.0f32, 0.0f32), |(min, max), &value| {
+            (min.min(value), max.max(value))
+        });
+
+        if self.symmetric {
+            let extent = min.abs().max(max.abs()).max(f32::EPSILON);
+            (-extent, extent)
+        } else {
+            (min, max.max(min + f32::EPSILON))
+        }
+    }
+}
+
+impl Widget for LinePlot<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let size = Vec2::new(ui.available_width(), self.height);
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals().clone();
+        painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+
+        let count = self.sample_count();
+        if count < 2 {
+            return response;
+        }
+
+        let (min, max) = self.value_range();
+        let x_at = |i: usize| rect.left() + rect.width() * i as f32 / (count - 1) as f32;
+        let y_at = |value: f32| rect.bottom() - rect.height() * (value - min) / (max - min);
+
+        if min < 0.0 {
+            let zero = y_at(0.0);
+            painter.hline(
+                rect.x_range(),
+                zero,
+                visuals.widgets.noninteractive.bg_stroke,
+            );
+        }
+
+        let band_height = 4.0;
+        for (i, color) in self.bands.iter().enumerate() {
+            let band = Rect::from_min_max(
+                Pos2::new(x_at(i), rect.bottom() - band_height),
+                Pos2::new(x_at(i + 1).min(rect.right()), rect.bottom()),
+            );
+            painter.rect_filled(band, 0.0, *color);
+        }
+
+        for (i, color) in &self.markers {
+            painter.vline(x_at(*i), rect.y_range(), Stroke::new(1.0, *color));
+        }
+
+        for series in &self.series {
+            let points = series
+                .values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| Pos2::new(x_at(i), y_at(*value)))
+                .collect();
+            painter.add(egui::Shape::line(points, Stroke::new(1.5, series.color)));
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            for series in &self.series {
+                ui.colored_label(series.color, series.name);
+            }
+            ui.weak(format!("{min:.2} – {max:.2}"));
+        });
+
+        match (&self.hover_text, response.hover_pos()) {
+            (Some(hover_text), Some(pos)) => {
+                let t = (pos.x - rect.left()) / rect.width();
+                let index = (t * (count - 1) as f32).round() as usize;
+                painter.vline(
+                    x_at(index),
+                    rect.y_range(),
+                    visuals.widgets.hovered.fg_stroke,
+                );
+                response.on_hover_text_at_pointer(hover_text(index))
+            }
+            _ => response,
+        }
+    }
+}
@@ -36,6 +36,14 @@ impl Widget for SimulationCell<'_> {
                 ui.label(self.cell.food.to_string());
                 ui.end_row();
 
+                ui.label("Wall");
+                if self.cell.flags.has_wall() {
+                    ui.label(egui_phosphor::regular::CHECK);
+                } else {
+                    ui.label(egui_phosphor::regular::X);
+                }
+                ui.end_row();
+
                 ui.label("Has Home");
                 if self.cell.flags.has_home() {
                     ui.label(egui_phosphor::regular::CHECK);
@@ -0,0 +1,36 @@
+use egui::{Color32, Response, Sense, Ui, Vec2, Widget};
+use lemon_antbox_core::simulation::display::DisplaySettings;
+
+/// A row of color swatches naming the tribes.
+pub struct TribeLegend<'a> {
+    display: &'a DisplaySettings,
+    tribe_count: u8,
+}
+
+impl<'a> TribeLegend<'a> {
+    pub fn new(display: &'a DisplaySettings, tribe_count: u8) -> Self {
+        Self {
+            display,
+            tribe_count,
+        }
+    }
+}
+
+impl Widget for TribeLegend<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.horizontal_wrapped(|ui| {
+            for tribe in 0..self.tribe_count {
+                let (rect, _) = ui.allocate_exact_size(Vec2::splat(10.0), Sense::hover());
+                ui.painter()
+                    .rect_filled(rect, 2.0, tribe_color32(self.display, tribe));
+                ui.small(format!("Tribe {tribe}"));
+            }
+        })
+        .response
+    }
+}
+
+pub fn tribe_color32(display: &DisplaySettings, tribe: u8) -> Color32 {
+    let [r, g, b] = display.tribe_color(tribe);
+    Color32::from_rgb(r, g, b)
+}
@@ -5,6 +5,10 @@ mod cell_inspector;
 mod display_settings;
 mod draw_settings;
 pub mod main;
+mod new_world;
+mod presets;
+mod recording;
+mod selection;
 mod simulation_settings;
 mod simulation_stats;
 
@@ -1,3 +1,4 @@
+use crate::ui::widgets::ant_history_plot::AntHistoryPlot;
 use crate::ui::widgets::simulation_ant::SimulationAnt;
 use crate::ui::widgets::simulation_cell::SimulationCell;
 use crate::ui::windows::{ToggleableUiWindow, UiWindow};
@@ -5,11 +6,27 @@ use egui::{Id, Ui, Widget, WidgetText};
 use lemon_antbox_core::threaded::event::InspectedCell;
 use lemon_antbox_core::threaded::ThreadedSimulation;
 
-#[derive(Default)]
 pub struct CellInspectorWindowState {
     pub is_open: bool,
     inspected_cell: InspectedCell,
     has_inspected: bool,
+    /// Keeps the inspected ant centered in the view.
+    pub follow: bool,
+    pub show_sensors: bool,
+    pub show_trail: bool,
+}
+
+impl Default for CellInspectorWindowState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            inspected_cell: InspectedCell::default(),
+            has_inspected: false,
+            follow: false,
+            show_sensors: true,
+            show_trail: true,
+        }
+    }
 }
 
 impl CellInspectorWindowState {
@@ -62,6 +79,19 @@ impl UiWindow for CellInspectorWindow<'_> {
             if let Some(buffer) = self.sim.inspected_ant() {
                 ui.separator();
                 SimulationAnt::new(&buffer.ant, &buffer.senses, 0).ui(ui);
+
+                ui.horizontal(|ui| {
+                    ui.toggle_value(
+                        &mut self.state.follow,
+                        format!("{} Follow", egui_phosphor::regular::CROSSHAIR),
+                    );
+                    ui.checkbox(&mut self.state.show_sensors, "Sensors");
+                    ui.checkbox(&mut self.state.show_trail, "Trail");
+                });
+
+                ui.collapsing("History", |ui| {
+                    AntHistoryPlot::new(&buffer.history).ui(ui);
+                });
             }
         });
     }
@@ -1,16 +1,113 @@
+use crate::ui::widgets::enum_select::EnumSelect;
 use crate::ui::widgets::option_enum_select::OptionEnumSelect;
 use crate::ui::windows::{ToggleableUiWindow, UiWindow};
-use egui::{Grid, Ui, Widget, WidgetText};
+use egui::{Button, DragValue, Grid, Slider, Ui, Widget, WidgetText};
+use lemon_antbox_core::simulation::display::{DisplaySettings, PheromoneLayer, RenderLayer};
+use lemon_antbox_core::simulation::pheromones::PheromoneType;
 use lemon_antbox_core::threaded::ThreadedSimulation;
 
+pub struct DisplaySettingsWindowState {
+    pub is_open: bool,
+    /// Marks food patches with icons when zoomed in.
+    pub food_icons: bool,
+    /// Sent to the simulation but not published back by it yet, shown instead of its settings
+    /// so the controls do not jump back in the meantime.
+    pending: Option<DisplaySettings>,
+}
+
+impl Default for DisplaySettingsWindowState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            food_icons: true,
+            pending: None,
+        }
+    }
+}
+
 pub struct DisplaySettingsWindow<'a> {
-    is_open: &'a mut bool,
-    sim: &'a ThreadedSimulation,
+    state: &'a mut DisplaySettingsWindowState,
+    sim: &'a mut ThreadedSimulation,
 }
 
 impl<'a> DisplaySettingsWindow<'a> {
-    pub fn new(is_open: &'a mut bool, sim: &'a ThreadedSimulation) -> Self {
-        Self { is_open, sim }
+    pub fn new(state: &'a mut DisplaySettingsWindowState, sim: &'a mut ThreadedSimulation) -> Self {
+        Self { state, sim }
+    }
+
+    fn render_tribe_colors(&self, ui: &mut Ui, display: &mut DisplaySettings) {
+        let tribe_count = self.sim.state().tribe_count();
+        let latest = self.sim.stats_history().latest(1).next();
+
+        Grid::new("tribe_colors_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for tribe in 0..tribe_count {
+                    let mut color = display.tribe_color(tribe);
+                    if ui.color_edit_button_srgb(&mut color).changed() {
+                        // Tribes past the palette share its colors until they get their own
+                        while display.tribe_colors.len() <= tribe as usize {
+                            let next = display.tribe_color(display.tribe_colors.len() as u8);
+                            display.tribe_colors.push(next);
+                        }
+                        display.tribe_colors[tribe as usize] = color;
+                    }
+                    ui.label(format!("Tribe {tribe}"));
+                    if let Some(stats) = latest.and_then(|s| s.tribes.get(tribe as usize)) {
+                        ui.weak(format!(
+                            "{} ants, {} delivered",
+                            stats.ant_count, stats.food_delivered
+                        ));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn render_overlay_layers(&self, ui: &mut Ui, display: &mut DisplaySettings) {
+        let max_tribe = self.sim.state().tribe_count().saturating_sub(1);
+        let mut removed = None;
+
+        Grid::new("overlay_layers_grid")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                for (i, layer) in display.overlay_layers.iter_mut().enumerate() {
+                    let mut visible = !layer.hidden;
+                    ui.checkbox(&mut visible, "");
+                    layer.hidden = !visible;
+                    ui.add(
+                        DragValue::new(&mut layer.tribe)
+                            .clamp_range(0..=max_tribe)
+                            .prefix("Tribe "),
+                    );
+                    let id = format!("overlay_layer_pheromone_{i}");
+                    EnumSelect::new(&mut layer.pheromone, &id).ui(ui);
+                    ui.color_edit_button_srgb(&mut layer.tint);
+                    if ui.button(egui_phosphor::regular::X).clicked() {
+                        removed = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(i) = removed {
+            display.overlay_layers.remove(i);
+        }
+
+        if ui
+            .button(format!("{} Add Layer", egui_phosphor::regular::PLUS))
+            .clicked()
+        {
+            let tribe = (display.overlay_layers.len() as u8).min(max_tribe);
+            display.overlay_layers.push(PheromoneLayer {
+                tribe,
+                pheromone: PheromoneType::Food,
+                tint: display.tribe_color(tribe),
+                hidden: false,
+            });
+        }
     }
 }
 
@@ -24,14 +121,25 @@ impl UiWindow for DisplaySettingsWindow<'_> {
     }
 
     fn is_open(&self) -> bool {
-        *self.is_open
+        self.state.is_open
     }
 
     fn set_open(&mut self, open: bool) {
-        *self.is_open = open;
+        self.state.is_open = open;
     }
 
     fn render_content(&mut self, ui: &mut Ui) {
+        let published = &self.sim.settings().display;
+        if self.state.pending.as_ref() == Some(published) {
+            self.state.pending = None;
+        }
+        let current = self
+            .state
+            .pending
+            .clone()
+            .unwrap_or_else(|| published.clone());
+        let mut display = current.clone();
+
         Grid::new("display_settings_grid")
             .num_columns(2)
             .striped(true)
@@ -41,7 +149,99 @@ impl UiWindow for DisplaySettingsWindow<'_> {
                 OptionEnumSelect::new(&mut drawn_pheromone, "Drawn Pheromone").ui(ui);
                 self.sim.state().set_drawn_pheromone(drawn_pheromone);
                 ui.end_row();
+
+                let max_tribe = self.sim.state().tribe_count().saturating_sub(1);
+                ui.label("Pheromone Tribe");
+                display.pheromone_tribe = display.pheromone_tribe.min(max_tribe);
+                ui.add_enabled(
+                    drawn_pheromone.is_some(),
+                    Slider::new(&mut display.pheromone_tribe, 0..=max_tribe),
+                );
+                ui.end_row();
+
+                ui.label("Colormap");
+                EnumSelect::new(&mut display.colormap, "display_colormap").ui(ui);
+                ui.end_row();
+
+                ui.label("Heat Scale");
+                EnumSelect::new(&mut display.heat_scale, "display_heat_scale").ui(ui);
+                ui.end_row();
+
+                ui.label("Auto Max Heat");
+                ui.checkbox(&mut display.auto_max_heat, "");
+                ui.end_row();
+
+                ui.label("Max Heat");
+                ui.add_enabled(
+                    !display.auto_max_heat,
+                    Slider::new(&mut display.max_heat, 0.1..=1000.0).logarithmic(true),
+                );
+                ui.end_row();
             });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut display.show_ants, "Ants");
+            ui.checkbox(&mut display.show_food, "Food");
+            ui.checkbox(&mut display.show_nests, "Nests");
+        });
+
+        ui.separator();
+        ui.label("Food");
+        ui.horizontal(|ui| {
+            ui.label("Gamma");
+            ui.add(Slider::new(&mut display.food_gamma, 0.1..=2.0));
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut display.food_outline, "Outlines");
+            ui.checkbox(&mut self.state.food_icons, "Icons when zoomed in");
+        });
+
+        ui.separator();
+        ui.label("Render Order");
+        display.layer_order = display.render_layers();
+        render_layer_order(ui, &mut display.layer_order);
+
+        ui.separator();
+        ui.label("Tribe Colors");
+        self.render_tribe_colors(ui, &mut display);
+
+        ui.separator();
+        ui.label("Overlay Layers");
+        self.render_overlay_layers(ui, &mut display);
+
+        if display != current {
+            self.sim.set_display_settings(display.clone());
+            self.state.pending = Some(display);
+        }
+    }
+}
+
+/// Lists the layers top to bottom, as they appear on screen, with buttons to move them.
+fn render_layer_order(ui: &mut Ui, layer_order: &mut [RenderLayer]) {
+    let mut raised = None;
+
+    for (i, layer) in layer_order.iter().enumerate().rev() {
+        ui.horizontal(|ui| {
+            let is_top = i + 1 == layer_order.len();
+            if ui
+                .add_enabled(!is_top, Button::new(egui_phosphor::regular::ARROW_UP))
+                .clicked()
+            {
+                raised = Some(i);
+            }
+            if ui
+                .add_enabled(i > 0, Button::new(egui_phosphor::regular::ARROW_DOWN))
+                .clicked()
+            {
+                raised = Some(i - 1);
+            }
+            ui.label(layer.to_string());
+        });
+    }
+
+    if let Some(i) = raised {
+        layer_order.swap(i, i + 1);
     }
 }
 
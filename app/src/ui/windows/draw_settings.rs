@@ -1,18 +1,58 @@
+use crate::ui::types::brush_shape::BrushShape;
 use crate::ui::types::draw_mode::DrawMode;
 use crate::ui::widgets::enum_select::EnumSelect;
 use crate::ui::windows::{ToggleableUiWindow, UiWindow};
 use egui::{Id, Slider, Ui, Widget, WidgetText};
+use lemon_antbox_core::simulation::region::RegionEdit;
 use lemon_antbox_core::threaded::ThreadedSimulation;
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct DrawSettingsWindowState {
     pub is_open: bool,
     pub draw_mode: DrawMode,
+    pub brush_shape: BrushShape,
+    pub brush_radius: u16,
     pub ant_tribe: u8,
+    pub ant_density: f32,
     pub nest_tribe: u8,
     pub food_amount: u8,
 }
 
+impl Default for DrawSettingsWindowState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            draw_mode: DrawMode::default(),
+            brush_shape: BrushShape::default(),
+            brush_radius: 0,
+            ant_tribe: 0,
+            ant_density: 1.0,
+            nest_tribe: 0,
+            food_amount: 1,
+        }
+    }
+}
+
+impl DrawSettingsWindowState {
+    /// What the brush does to the cells it covers.
+    pub fn brush_edit(&self) -> RegionEdit {
+        match self.draw_mode {
+            DrawMode::Ant => RegionEdit::Ants {
+                tribe: self.ant_tribe,
+                density: self.ant_density,
+            },
+            DrawMode::Nest => RegionEdit::Nest {
+                tribe: self.nest_tribe,
+            },
+            DrawMode::Food => RegionEdit::Food {
+                amount: self.food_amount,
+            },
+            DrawMode::Wall => RegionEdit::Wall,
+            DrawMode::Eraser => RegionEdit::Erase,
+        }
+    }
+}
+
 pub struct DrawSettingsWindow<'a> {
     state: &'a mut DrawSettingsWindowState,
     sim: &'a ThreadedSimulation,
@@ -43,10 +83,45 @@ impl UiWindow for DrawSettingsWindow<'_> {
 
     fn render_content(&mut self, ui: &mut Ui) {
         ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                let state = self.sim.state();
+                if ui
+                    .add_enabled(
+                        state.can_undo(),
+                        egui::Button::new(egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE),
+                    )
+                    .on_hover_text("Undo (Ctrl+Z)")
+                    .clicked()
+                {
+                    self.sim.undo();
+                }
+                if ui
+                    .add_enabled(
+                        state.can_redo(),
+                        egui::Button::new(egui_phosphor::regular::ARROW_CLOCKWISE),
+                    )
+                    .on_hover_text("Redo (Ctrl+Y)")
+                    .clicked()
+                {
+                    self.sim.redo();
+                }
+            });
+
             EnumSelect::new(&mut self.state.draw_mode, "draw_settings_draw_mode")
                 .label("Draw Mode")
                 .ui(ui);
 
+            EnumSelect::new(&mut self.state.brush_shape, "draw_settings_brush_shape")
+                .label("Brush")
+                .ui(ui);
+
+            if self.state.brush_shape != BrushShape::Rectangle {
+                ui.horizontal(|ui| {
+                    ui.label("Radius");
+                    ui.add(Slider::new(&mut self.state.brush_radius, 0..=50));
+                });
+            }
+
             let max_tribe = self.sim.state().tribe_count().saturating_sub(1);
             match self.state.draw_mode {
                 DrawMode::Ant => {
@@ -54,6 +129,14 @@ impl UiWindow for DrawSettingsWindow<'_> {
                         ui.label("Tribe");
                         ui.add(Slider::new(&mut self.state.ant_tribe, 0..=max_tribe));
                     });
+                    ui.horizontal(|ui| {
+                        ui.label("Density");
+                        ui.add(
+                            Slider::new(&mut self.state.ant_density, 0.01..=5.0)
+                                .logarithmic(true)
+                                .suffix(" per cell"),
+                        );
+                    });
                 }
                 DrawMode::Nest => {
                     ui.horizontal(|ui| {
@@ -67,6 +150,7 @@ impl UiWindow for DrawSettingsWindow<'_> {
                         ui.add(Slider::new(&mut self.state.food_amount, 1..=255));
                     });
                 }
+                DrawMode::Wall | DrawMode::Eraser => {}
             }
         });
     }
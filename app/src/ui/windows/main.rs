@@ -1,19 +1,28 @@
+use crate::ui::widgets::tribe_legend::TribeLegend;
 use crate::ui::windows::cell_inspector::{CellInspectorWindow, CellInspectorWindowState};
-use crate::ui::windows::display_settings::DisplaySettingsWindow;
+use crate::ui::windows::display_settings::{DisplaySettingsWindow, DisplaySettingsWindowState};
 use crate::ui::windows::draw_settings::{DrawSettingsWindow, DrawSettingsWindowState};
-use crate::ui::windows::simulation_settings::SimulationSettingsWindow;
-use crate::ui::windows::simulation_stats::SimulationStatsWindow;
+use crate::ui::windows::new_world::{NewWorldWindow, NewWorldWindowState};
+use crate::ui::windows::presets::{PresetsWindow, PresetsWindowState};
+use crate::ui::windows::recording::{RecordingWindow, RecordingWindowState};
+use crate::ui::windows::selection::{SelectionWindow, SelectionWindowState};
+use crate::ui::windows::simulation_settings::{SimulationSettingsWindow, SimulationSettingsWindowState};
+use crate::ui::windows::simulation_stats::{SimulationStatsWindow, SimulationStatsWindowState};
 use crate::ui::windows::{ToggleableUiWindow, UiWindow};
-use egui::{Id, Ui, WidgetText};
+use egui::{Id, Ui, Widget, WidgetText};
 use lemon_antbox_core::threaded::ThreadedSimulation;
 
 pub struct MainWindowState {
     pub is_open: bool,
     pub cell_inspector_window_state: CellInspectorWindowState,
+    pub selection: SelectionWindowState,
     pub draw_settings: DrawSettingsWindowState,
-    display_settings_open: bool,
-    simulation_settings_open: bool,
-    simulation_stats_open: bool,
+    pub recording: RecordingWindowState,
+    pub simulation_settings: SimulationSettingsWindowState,
+    pub presets: PresetsWindowState,
+    pub new_world: NewWorldWindowState,
+    simulation_stats: SimulationStatsWindowState,
+    pub display_settings: DisplaySettingsWindowState,
 }
 
 impl Default for MainWindowState {
@@ -21,10 +30,14 @@ impl Default for MainWindowState {
         Self {
             is_open: true,
             cell_inspector_window_state: CellInspectorWindowState::default(),
+            selection: SelectionWindowState::default(),
             draw_settings: DrawSettingsWindowState::default(),
-            display_settings_open: false,
-            simulation_settings_open: false,
-            simulation_stats_open: false,
+            recording: RecordingWindowState::default(),
+            simulation_settings: SimulationSettingsWindowState::default(),
+            presets: PresetsWindowState::default(),
+            new_world: NewWorldWindowState::default(),
+            simulation_stats: SimulationStatsWindowState::default(),
+            display_settings: DisplaySettingsWindowState::default(),
         }
     }
 }
@@ -62,18 +75,33 @@ impl UiWindow for MainWindow<'_> {
             DrawSettingsWindow::new(&mut self.state.draw_settings, self.sim)
                 .toggle_button(ui)
                 .show(ui.ctx());
-            SimulationSettingsWindow::new(&mut self.state.simulation_settings_open, self.sim)
+            SimulationSettingsWindow::new(&mut self.state.simulation_settings, self.sim)
                 .toggle_button(ui)
                 .show(ui.ctx());
-            DisplaySettingsWindow::new(&mut self.state.display_settings_open, self.sim)
+            NewWorldWindow::new(&mut self.state.new_world, self.sim)
+                .toggle_button(ui)
+                .show(ui.ctx());
+            PresetsWindow::new(&mut self.state.presets, self.sim)
+                .toggle_button(ui)
+                .show(ui.ctx());
+            DisplaySettingsWindow::new(&mut self.state.display_settings, self.sim)
                 .toggle_button(ui)
                 .show(ui.ctx());
             CellInspectorWindow::new(&mut self.state.cell_inspector_window_state, &mut self.sim)
                 .toggle_button(ui)
                 .show(ui.ctx());
-            SimulationStatsWindow::new(&mut self.state.simulation_stats_open, self.sim)
+            SelectionWindow::new(&mut self.state.selection, self.sim)
+                .toggle_button(ui)
+                .show(ui.ctx());
+            SimulationStatsWindow::new(&mut self.state.simulation_stats, self.sim)
+                .toggle_button(ui)
+                .show(ui.ctx());
+            RecordingWindow::new(&mut self.state.recording, self.sim)
                 .toggle_button(ui)
                 .show(ui.ctx());
         });
+
+        let tribe_count = self.sim.state().tribe_count();
+        TribeLegend::new(&self.sim.settings().display, tribe_count).ui(ui);
     }
 }
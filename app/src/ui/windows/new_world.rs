@@ -0,0 +1,116 @@
+use crate::ui::windows::{ToggleableUiWindow, UiWindow};
+use egui::{DragValue, Grid, Id, Slider, Ui, WidgetText};
+use lemon_antbox_core::simulation::settings::SimulationSettings;
+use lemon_antbox_core::threaded::ThreadedSimulation;
+
+pub struct NewWorldWindowState {
+    pub is_open: bool,
+    pub width: u16,
+    pub height: u16,
+    pub tribe_count: u8,
+    pub new_seed: bool,
+    /// Settings of a world to be created, picked up by the app after the UI is drawn.
+    pub requested: Option<SimulationSettings>,
+}
+
+impl Default for NewWorldWindowState {
+    fn default() -> Self {
+        let settings = SimulationSettings::default();
+        Self {
+            is_open: false,
+            width: settings.width,
+            height: settings.height,
+            tribe_count: settings.tribe_count,
+            new_seed: true,
+            requested: None,
+        }
+    }
+}
+
+pub struct NewWorldWindow<'a> {
+    state: &'a mut NewWorldWindowState,
+    sim: &'a mut ThreadedSimulation,
+}
+
+impl<'a> NewWorldWindow<'a> {
+    pub fn new(state: &'a mut NewWorldWindowState, sim: &'a mut ThreadedSimulation) -> Self {
+        Self { state, sim }
+    }
+
+    fn use_current_world(&mut self) {
+        self.state.width = self.sim.width();
+        self.state.height = self.sim.height();
+        self.state.tribe_count = self.sim.state().tribe_count();
+    }
+}
+
+impl UiWindow for NewWorldWindow<'_> {
+    fn id() -> Id {
+        Id::new("new_world_window")
+    }
+
+    fn title() -> impl Into<WidgetText> {
+        "New World"
+    }
+
+    fn is_open(&self) -> bool {
+        self.state.is_open
+    }
+
+    fn set_open(&mut self, open: bool) {
+        // Every time the window opens it starts out from the world that is running
+        if open && !self.state.is_open {
+            self.use_current_world();
+        }
+        self.state.is_open = open;
+    }
+
+    fn render_content(&mut self, ui: &mut Ui) {
+        Grid::new("new_world_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Width");
+                ui.add(DragValue::new(&mut self.state.width).clamp_range(16..=4096));
+                ui.end_row();
+
+                ui.label("Height");
+                ui.add(DragValue::new(&mut self.state.height).clamp_range(16..=4096));
+                ui.end_row();
+
+                ui.label("Tribes");
+                ui.add(Slider::new(&mut self.state.tribe_count, 1..=16));
+                ui.end_row();
+
+                ui.label("New Seed");
+                ui.checkbox(&mut self.state.new_seed, "");
+                ui.end_row();
+            });
+
+        ui.horizontal(|ui| {
+            if ui.button("Use Current Size").clicked() {
+                self.use_current_world();
+            }
+
+            if ui.button("Create").clicked() {
+                // Everything but the world itself carries over from the current simulation
+                let mut settings = self.sim.settings().clone();
+                settings.width = self.state.width;
+                settings.height = self.state.height;
+                settings.tribe_count = self.state.tribe_count;
+                settings.display.pheromone_tribe = 0;
+                settings.inspected_ant = None;
+                if self.state.new_seed {
+                    settings.seed = SimulationSettings::default().seed;
+                }
+                self.state.requested = Some(settings);
+            }
+        });
+    }
+}
+
+impl ToggleableUiWindow for NewWorldWindow<'_> {
+    fn toggle_label(&self) -> String {
+        egui_phosphor::regular::GLOBE_HEMISPHERE_WEST.to_string()
+    }
+}
@@ -0,0 +1,165 @@
+use crate::presets::{presets_directory, DEFAULT_PRESET};
+use crate::ui::windows::{ToggleableUiWindow, UiWindow};
+use egui::{Color32, Id, ScrollArea, Ui, WidgetText};
+use lemon_antbox_core::preset::PresetStore;
+use lemon_antbox_core::threaded::ThreadedSimulation;
+
+pub struct PresetsWindowState {
+    pub is_open: bool,
+    store: PresetStore,
+    names: Vec<String>,
+    name: String,
+    error: Option<String>,
+}
+
+impl Default for PresetsWindowState {
+    fn default() -> Self {
+        let mut state = Self {
+            is_open: false,
+            store: PresetStore::new(presets_directory()),
+            names: Vec::new(),
+            name: String::new(),
+            error: None,
+        };
+        state.refresh();
+        state
+    }
+}
+
+impl PresetsWindowState {
+    fn refresh(&mut self) {
+        match self.store.list() {
+            Ok(names) => self.names = names,
+            Err(err) => self.error = Some(err.to_string()),
+        }
+    }
+
+    fn save(&mut self, sim: &mut ThreadedSimulation) {
+        let result = self.store.save(&self.name, sim.settings());
+        self.error = result.err().map(|err| err.to_string());
+        self.refresh();
+    }
+
+    fn load(&mut self, sim: &ThreadedSimulation) {
+        match self.store.load(&self.name) {
+            Ok(settings) => {
+                sim.apply_settings(&settings);
+                self.error = None;
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+    }
+
+    fn delete(&mut self) {
+        let result = self.store.delete(&self.name);
+        self.error = result.err().map(|err| err.to_string());
+        self.refresh();
+    }
+}
+
+pub struct PresetsWindow<'a> {
+    state: &'a mut PresetsWindowState,
+    sim: &'a mut ThreadedSimulation,
+}
+
+impl<'a> PresetsWindow<'a> {
+    pub fn new(state: &'a mut PresetsWindowState, sim: &'a mut ThreadedSimulation) -> Self {
+        Self { state, sim }
+    }
+}
+
+impl UiWindow for PresetsWindow<'_> {
+    fn id() -> Id {
+        Id::new("presets_window")
+    }
+
+    fn title() -> impl Into<WidgetText> {
+        "Presets"
+    }
+
+    fn is_open(&self) -> bool {
+        self.state.is_open
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.state.is_open = open;
+    }
+
+    fn render_content(&mut self, ui: &mut Ui) {
+        ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+            if self.state.names.is_empty() {
+                ui.weak("No presets saved yet");
+            }
+
+            for name in &self.state.names {
+                let selected = *name == self.state.name;
+                if ui.selectable_label(selected, name).clicked() {
+                    self.state.name = name.clone();
+                }
+            }
+        });
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label("Name");
+            ui.text_edit_singleline(&mut self.state.name);
+        });
+
+        let exists = self.state.names.contains(&self.state.name);
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    !self.state.name.is_empty(),
+                    egui::Button::new(egui_phosphor::regular::FLOPPY_DISK),
+                )
+                .on_hover_text("Save")
+                .clicked()
+            {
+                self.state.save(self.sim);
+            }
+
+            if ui
+                .add_enabled(
+                    exists,
+                    egui::Button::new(egui_phosphor::regular::UPLOAD_SIMPLE),
+                )
+                .on_hover_text("Load")
+                .clicked()
+            {
+                self.state.load(self.sim);
+            }
+
+            if ui
+                .add_enabled(exists, egui::Button::new(egui_phosphor::regular::TRASH))
+                .on_hover_text("Delete")
+                .clicked()
+            {
+                self.state.delete();
+            }
+
+            if ui
+                .button(egui_phosphor::regular::ARROWS_CLOCKWISE)
+                .on_hover_text("Refresh")
+                .clicked()
+            {
+                self.state.refresh();
+            }
+        });
+
+        ui.small(format!(
+            "World size and tribe count only apply to new worlds. \
+             The \"{DEFAULT_PRESET}\" preset is loaded on startup."
+        ));
+
+        if let Some(error) = &self.state.error {
+            ui.colored_label(Color32::RED, error);
+        }
+    }
+}
+
+impl ToggleableUiWindow for PresetsWindow<'_> {
+    fn toggle_label(&self) -> String {
+        egui_phosphor::regular::BOOKMARKS.to_string()
+    }
+}
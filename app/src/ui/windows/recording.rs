@@ -0,0 +1,228 @@
+use crate::ui::widgets::enum_select::EnumSelect;
+use crate::ui::windows::{ToggleableUiWindow, UiWindow};
+use egui::{Grid, Id, Slider, Ui, Widget, WidgetText};
+use lemon_antbox_core::export::animation::{AnimationFormat, AnimationSettings};
+use lemon_antbox_core::threaded::ThreadedSimulation;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub struct RecordingWindowState {
+    pub is_open: bool,
+    pub scale: u32,
+    pub directory: String,
+    pub every_n_steps: u64,
+    pub is_recording_frames: bool,
+    pub animation_format: AnimationFormat,
+    pub animation_fps: u16,
+    pub animation_max_secs: u32,
+    pub quantization_speed: i32,
+    pub is_recording_animation: bool,
+    pub replay_path: String,
+}
+
+impl Default for RecordingWindowState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            scale: 2,
+            directory: "recordings".to_string(),
+            every_n_steps: 10,
+            is_recording_frames: false,
+            animation_format: AnimationFormat::default(),
+            animation_fps: 30,
+            animation_max_secs: 20,
+            quantization_speed: 10,
+            is_recording_animation: false,
+            replay_path: String::new(),
+        }
+    }
+}
+
+impl RecordingWindowState {
+    pub fn screenshot(&self, sim: &mut ThreadedSimulation) {
+        let path = self.timestamped_path("screenshot").with_extension("png");
+        if let Some(parent) = path.parent()
+            && let Err(err) = std::fs::create_dir_all(parent)
+        {
+            eprintln!("Failed to create {}: {err}", parent.display());
+            return;
+        }
+
+        if let Err(err) = sim.screenshot(&path, self.scale) {
+            eprintln!("Failed to save screenshot {}: {err}", path.display());
+        }
+    }
+
+    fn timestamped_path(&self, prefix: &str) -> PathBuf {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        PathBuf::from(&self.directory).join(format!("{prefix}_{timestamp}"))
+    }
+}
+
+pub struct RecordingWindow<'a> {
+    state: &'a mut RecordingWindowState,
+    sim: &'a mut ThreadedSimulation,
+}
+
+impl<'a> RecordingWindow<'a> {
+    pub fn new(state: &'a mut RecordingWindowState, sim: &'a mut ThreadedSimulation) -> Self {
+        Self { state, sim }
+    }
+}
+
+impl RecordingWindow<'_> {
+    fn start_animation_recording(&mut self) {
+        let format = self.state.animation_format;
+        let path = self
+            .state
+            .timestamped_path("animation")
+            .with_extension(format.extension());
+        if let Err(err) = std::fs::create_dir_all(&self.state.directory) {
+            eprintln!("Failed to create {}: {err}", self.state.directory);
+            return;
+        }
+
+        let settings = AnimationSettings {
+            every_n_steps: self.state.every_n_steps,
+            frames_per_second: self.state.animation_fps,
+            scale: self.state.scale,
+            max_duration: Duration::from_secs(self.state.animation_max_secs as u64),
+            quantization_speed: self.state.quantization_speed,
+        };
+        self.sim.start_animation_recording(path, format, settings);
+        self.state.is_recording_animation = true;
+    }
+}
+
+impl UiWindow for RecordingWindow<'_> {
+    fn id() -> Id {
+        Id::new("recording_window")
+    }
+
+    fn title() -> impl Into<WidgetText> {
+        "Recording"
+    }
+
+    fn is_open(&self) -> bool {
+        self.state.is_open
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.state.is_open = open;
+    }
+
+    fn render_content(&mut self, ui: &mut Ui) {
+        Grid::new("recording_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Directory");
+                ui.text_edit_singleline(&mut self.state.directory);
+                ui.end_row();
+
+                ui.label("Scale");
+                ui.add(Slider::new(&mut self.state.scale, 1..=8).suffix("x"));
+                ui.end_row();
+
+                ui.label("Every N Steps");
+                ui.add_enabled(
+                    !self.state.is_recording_frames && !self.state.is_recording_animation,
+                    Slider::new(&mut self.state.every_n_steps, 1..=1000).logarithmic(true),
+                );
+                ui.end_row();
+
+                ui.label("Animation Format");
+                EnumSelect::new(
+                    &mut self.state.animation_format,
+                    "recording_animation_format",
+                )
+                .ui(ui);
+                ui.end_row();
+
+                ui.label("Animation FPS");
+                ui.add(Slider::new(&mut self.state.animation_fps, 1..=60));
+                ui.end_row();
+
+                ui.label("Max Duration");
+                ui.add(Slider::new(&mut self.state.animation_max_secs, 1..=120).suffix("s"));
+                ui.end_row();
+
+                if self.state.animation_format == AnimationFormat::Gif {
+                    ui.label("Quantization Speed");
+                    ui.add(Slider::new(&mut self.state.quantization_speed, 1..=30))
+                        .on_hover_text("1 gives the best colors, 30 is the fastest");
+                    ui.end_row();
+                }
+            });
+
+        ui.horizontal(|ui| {
+            if ui
+                .button(egui_phosphor::regular::CAMERA)
+                .on_hover_text("Screenshot (F12)")
+                .clicked()
+            {
+                self.state.screenshot(self.sim);
+            }
+
+            if self.state.is_recording_frames {
+                if ui.button("Stop Frames").clicked() {
+                    self.sim.stop_frame_recording();
+                    self.state.is_recording_frames = false;
+                }
+            } else if ui.button("Record Frames").clicked() {
+                self.sim.start_frame_recording(
+                    self.state.timestamped_path("frames"),
+                    self.state.every_n_steps,
+                    self.state.scale,
+                );
+                self.state.is_recording_frames = true;
+            }
+
+            if self.state.is_recording_animation {
+                if ui.button("Stop Animation").clicked() {
+                    self.sim.stop_animation_recording();
+                }
+            } else if ui.button("Record Animation").clicked() {
+                self.start_animation_recording();
+            }
+        });
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label("Replay");
+            ui.text_edit_singleline(&mut self.state.replay_path);
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Save Replay").clicked() {
+                let path = self
+                    .state
+                    .timestamped_path("replay")
+                    .with_extension("antreplay");
+                self.state.replay_path = path.display().to_string();
+                if let Err(err) = std::fs::create_dir_all(&self.state.directory) {
+                    eprintln!("Failed to create {}: {err}", self.state.directory);
+                } else {
+                    self.sim.save_replay(path);
+                }
+            }
+
+            let can_play = !self.state.replay_path.is_empty();
+            if ui
+                .add_enabled(can_play, egui::Button::new("Play Replay"))
+                .clicked()
+            {
+                self.sim.play_replay(&self.state.replay_path);
+            }
+        });
+    }
+}
+
+impl ToggleableUiWindow for RecordingWindow<'_> {
+    fn toggle_label(&self) -> String {
+        egui_phosphor::regular::VIDEO_CAMERA.to_string()
+    }
+}
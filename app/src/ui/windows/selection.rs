@@ -0,0 +1,177 @@
+use crate::ui::windows::{ToggleableUiWindow, UiWindow};
+use egui::{ComboBox, Grid, Id, Slider, Ui, WidgetText};
+use lemon_antbox_core::simulation::ant::AntMode;
+use lemon_antbox_core::simulation::group::{AntGroupEdit, AntGroupStats};
+use lemon_antbox_core::threaded::ThreadedSimulation;
+
+const MODES: [AntMode; 3] = [
+    AntMode::Exploring,
+    AntMode::FoodToHome,
+    AntMode::SearchingHome,
+];
+
+pub struct SelectionWindowState {
+    pub is_open: bool,
+    stats: AntGroupStats,
+    /// Refreshes the stats every frame while the window is open.
+    live: bool,
+    tribe: u8,
+    mode: AntMode,
+}
+
+impl Default for SelectionWindowState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            stats: AntGroupStats::default(),
+            live: true,
+            tribe: 0,
+            mode: AntMode::default(),
+        }
+    }
+}
+
+impl SelectionWindowState {
+    pub fn set_stats(&mut self, stats: AntGroupStats) {
+        self.stats = stats;
+    }
+}
+
+pub struct SelectionWindow<'a> {
+    state: &'a mut SelectionWindowState,
+    sim: &'a ThreadedSimulation,
+}
+
+impl<'a> SelectionWindow<'a> {
+    pub fn new(state: &'a mut SelectionWindowState, sim: &'a ThreadedSimulation) -> Self {
+        Self { state, sim }
+    }
+
+    fn render_stats(&self, ui: &mut Ui) {
+        let stats = &self.state.stats;
+        let share = |count: u32| {
+            format!(
+                "{count} ({:.0}%)",
+                100.0 * count as f32 / stats.count as f32
+            )
+        };
+
+        Grid::new("selection_stats_grid")
+            .striped(true)
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Ants");
+                ui.label(stats.count.to_string());
+                ui.end_row();
+
+                ui.label("Carrying food");
+                ui.label(share(stats.carrying_food));
+                ui.end_row();
+
+                for mode in MODES {
+                    ui.label(format!("{mode:?}"));
+                    ui.label(share(stats.mode_count(mode)));
+                    ui.end_row();
+                }
+
+                ui.label("Mean heading");
+                match stats.mean_heading {
+                    Some(heading) => ui.label(format!(
+                        "{:.1}° (alignment {:.2})",
+                        heading.to_degrees(),
+                        stats.heading_alignment
+                    )),
+                    None => ui.label("-"),
+                };
+                ui.end_row();
+
+                for (tribe, count) in stats.tribes.iter().enumerate() {
+                    if *count > 0 {
+                        ui.label(format!("Tribe {tribe}"));
+                        ui.label(share(*count));
+                        ui.end_row();
+                    }
+                }
+            });
+    }
+
+    fn render_actions(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let max_tribe = self.sim.state().tribe_count().saturating_sub(1);
+            self.state.tribe = self.state.tribe.min(max_tribe);
+            ui.add(Slider::new(&mut self.state.tribe, 0..=max_tribe).text("Tribe"));
+            if ui.button("Set Tribe").clicked() {
+                let tribe = self.state.tribe;
+                self.sim.edit_selection(AntGroupEdit::Tribe { tribe });
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source("selection_mode")
+                .selected_text(format!("{:?}", self.state.mode))
+                .show_ui(ui, |ui| {
+                    for mode in MODES {
+                        ui.selectable_value(&mut self.state.mode, mode, format!("{mode:?}"));
+                    }
+                });
+            if ui.button("Force Mode").clicked() {
+                let mode = self.state.mode;
+                self.sim.edit_selection(AntGroupEdit::Mode { mode });
+            }
+        });
+
+        ui.horizontal(|ui| {
+            if ui
+                .button(format!("{} Delete", egui_phosphor::regular::TRASH))
+                .clicked()
+            {
+                self.sim.edit_selection(AntGroupEdit::Delete);
+            }
+            if ui.button("Deselect").clicked() {
+                self.sim.clear_selection();
+            }
+        });
+    }
+}
+
+impl UiWindow for SelectionWindow<'_> {
+    fn id() -> Id {
+        Id::new("selection_window")
+    }
+
+    fn title() -> impl Into<WidgetText> {
+        "Selection"
+    }
+
+    fn is_open(&self) -> bool {
+        self.state.is_open
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.state.is_open = open;
+    }
+
+    fn render_content(&mut self, ui: &mut Ui) {
+        if self.state.live && self.state.stats.count > 0 {
+            self.sim.inspect_selection();
+        }
+
+        ui.vertical(|ui| {
+            if self.state.stats.count == 0 {
+                ui.small("Shift-drag over the world to select ants.");
+                return;
+            }
+
+            self.render_stats(ui);
+            ui.checkbox(&mut self.state.live, "Live");
+            ui.separator();
+            self.render_actions(ui);
+        });
+    }
+}
+
+impl ToggleableUiWindow for SelectionWindow<'_> {
+    fn toggle_label(&self) -> String {
+        egui_phosphor::regular::SELECTION.into()
+    }
+}
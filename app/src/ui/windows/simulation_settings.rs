@@ -1,15 +1,240 @@
 use crate::ui::windows::{ToggleableUiWindow, UiWindow};
-use egui::{Id, Ui, Widget, WidgetText};
+use egui::{Grid, Id, Slider, Ui, Widget, WidgetText};
+use lemon_antbox_core::checkpoint::CheckpointSettings;
+use lemon_antbox_core::simulation::settings::{SettingsPatch, SimulationSettings};
 use lemon_antbox_core::threaded::ThreadedSimulation;
+use std::f32::consts::PI;
+use std::ops::RangeInclusive;
+
+const BYTES_PER_MB: usize = 1024 * 1024;
+
+pub struct SimulationSettingsWindowState {
+    pub is_open: bool,
+    pub run_steps: u64,
+    pub rewind_step: u64,
+    pub checkpoint_interval: u64,
+    pub checkpoint_budget_mb: usize,
+}
+
+impl Default for SimulationSettingsWindowState {
+    fn default() -> Self {
+        let checkpoints = CheckpointSettings::default();
+        Self {
+            is_open: false,
+            run_steps: 100,
+            rewind_step: 0,
+            checkpoint_interval: checkpoints.interval,
+            checkpoint_budget_mb: checkpoints.memory_budget_bytes / BYTES_PER_MB,
+        }
+    }
+}
+
+impl SimulationSettingsWindowState {
+    pub fn checkpoint_settings(&self) -> CheckpointSettings {
+        CheckpointSettings {
+            interval: self.checkpoint_interval,
+            memory_budget_bytes: self.checkpoint_budget_mb * BYTES_PER_MB,
+        }
+    }
+}
 
 pub struct SimulationSettingsWindow<'a> {
-    is_open: &'a mut bool,
-    sim: &'a ThreadedSimulation,
+    state: &'a mut SimulationSettingsWindowState,
+    sim: &'a mut ThreadedSimulation,
 }
 
 impl<'a> SimulationSettingsWindow<'a> {
-    pub fn new(is_open: &'a mut bool, sim: &'a ThreadedSimulation) -> Self {
-        Self { is_open, sim }
+    pub fn new(
+        state: &'a mut SimulationSettingsWindowState,
+        sim: &'a mut ThreadedSimulation,
+    ) -> Self {
+        Self { state, sim }
+    }
+
+    fn render_behavior(&mut self, ui: &mut Ui) {
+        let settings = self.sim.settings().clone();
+        let mut patch = SettingsPatch::default();
+
+        Grid::new("behavior_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                let ant = &settings.ant;
+                patch_slider(
+                    ui,
+                    "Pheromone Strength",
+                    ant.pheromone_strength,
+                    0.0..=10.0,
+                    &mut patch.ant.pheromone_strength,
+                );
+                patch_slider(
+                    ui,
+                    "Sensor Angle",
+                    ant.sensor_angle,
+                    0.0..=PI,
+                    &mut patch.ant.sensor_angle,
+                );
+                patch_slider(
+                    ui,
+                    "Sensor Distance",
+                    ant.sensor_distance,
+                    0.0..=50.0,
+                    &mut patch.ant.sensor_distance,
+                );
+                patch_slider(ui, "Speed", ant.speed, 0.1..=5.0, &mut patch.ant.speed);
+                patch_slider(
+                    ui,
+                    "Turn Angle",
+                    ant.turn_angle,
+                    0.0..=PI,
+                    &mut patch.ant.turn_angle,
+                );
+                patch_slider(
+                    ui,
+                    "Wobble Strength",
+                    ant.wobble_strength,
+                    0.0..=2.0,
+                    &mut patch.ant.wobble_strength,
+                );
+                patch_slider(
+                    ui,
+                    "Spiral Expansion",
+                    ant.spiral_expansion_rate,
+                    0.0..=0.1,
+                    &mut patch.ant.spiral_expansion_rate,
+                );
+                patch_slider(
+                    ui,
+                    "Pheromone Decay",
+                    settings.pheromone_decay,
+                    0.9..=1.0,
+                    &mut patch.pheromone_decay,
+                );
+                patch_slider(
+                    ui,
+                    "Pheromone Diffusion",
+                    settings.pheromone_diffusion,
+                    0.0..=1.0,
+                    &mut patch.pheromone_diffusion,
+                );
+                patch_slider(
+                    ui,
+                    "Nest Strength",
+                    settings.nest_pheromone_strength,
+                    0.0..=20.0,
+                    &mut patch.nest_pheromone_strength,
+                );
+            });
+
+        if ui.button("Reset to Defaults").clicked() {
+            patch = SettingsPatch::from_settings(&SimulationSettings::default());
+        }
+
+        if !patch.is_empty() {
+            self.sim.update_settings(patch);
+        }
+    }
+
+    fn render_speed(&mut self, ui: &mut Ui) {
+        let state = self.sim.state();
+
+        Grid::new("speed_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Steps per Second");
+                let mut steps_per_second = state.steps_per_second();
+                ui.add_enabled(
+                    !state.is_fast_forward(),
+                    Slider::new(&mut steps_per_second, 1..=255),
+                );
+                state.set_steps_per_second(steps_per_second);
+                ui.end_row();
+
+                ui.label("Speed Multiplier");
+                let mut speed_multiplier = state.speed_multiplier();
+                ui.add_enabled(
+                    !state.is_fast_forward(),
+                    Slider::new(&mut speed_multiplier, 1..=100)
+                        .logarithmic(true)
+                        .suffix("x"),
+                );
+                state.set_speed_multiplier(speed_multiplier);
+                ui.end_row();
+
+                ui.label("Fast Forward");
+                let mut fast_forward = state.is_fast_forward();
+                egui::Checkbox::new(&mut fast_forward, egui_phosphor::regular::FAST_FORWARD)
+                    .ui(ui)
+                    .on_hover_text("Run as many steps as possible");
+                state.set_fast_forward(fast_forward);
+                ui.end_row();
+            });
+    }
+
+    fn render_checkpoints(&mut self, ui: &mut Ui) {
+        let mut changed = false;
+
+        Grid::new("checkpoint_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Checkpoint Every");
+                changed |= ui
+                    .add(
+                        Slider::new(&mut self.state.checkpoint_interval, 10..=10000)
+                            .logarithmic(true)
+                            .suffix(" steps"),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Memory Budget");
+                changed |= ui
+                    .add(
+                        Slider::new(&mut self.state.checkpoint_budget_mb, 16..=4096)
+                            .logarithmic(true)
+                            .suffix(" MB"),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Stored");
+                ui.label(format!(
+                    "{} checkpoints, {:.1} MB",
+                    self.sim.state().checkpoint_count(),
+                    self.sim.state().checkpoint_bytes() as f64 / BYTES_PER_MB as f64
+                ));
+                ui.end_row();
+            });
+
+        if changed {
+            self.sim
+                .set_checkpoint_settings(self.state.checkpoint_settings());
+        }
+
+        let current_step = self.sim.state().step();
+        let Some(oldest_step) = self.sim.state().oldest_checkpoint_step() else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.add_enabled(
+                oldest_step < current_step,
+                Slider::new(&mut self.state.rewind_step, oldest_step..=current_step).text("Step"),
+            );
+
+            if ui
+                .add_enabled(
+                    self.state.rewind_step < current_step,
+                    egui::Button::new(egui_phosphor::regular::CLOCK_COUNTER_CLOCKWISE),
+                )
+                .on_hover_text("Rewind")
+                .clicked()
+            {
+                self.sim.rewind_to(self.state.rewind_step);
+            }
+        });
     }
 }
 
@@ -23,11 +248,11 @@ impl UiWindow for SimulationSettingsWindow<'_> {
     }
 
     fn is_open(&self) -> bool {
-        *self.is_open
+        self.state.is_open
     }
 
     fn set_open(&mut self, open: bool) {
-        *self.is_open = open;
+        self.state.is_open = open;
     }
 
     fn render_content(&mut self, ui: &mut Ui) {
@@ -36,10 +261,43 @@ impl UiWindow for SimulationSettingsWindow<'_> {
             egui::Checkbox::new(&mut paused, "Paused").ui(ui);
             self.sim.state().set_paused(paused);
 
+            if ui
+                .button(egui_phosphor::regular::SKIP_FORWARD)
+                .on_hover_text("Step (.)")
+                .clicked()
+            {
+                self.sim.step(1);
+            }
+
             if ui.button("Clear").clicked() {
                 self.sim.clear();
             }
         });
+
+        ui.horizontal(|ui| {
+            ui.label(format!("Step {}", self.sim.state().step()));
+
+            ui.add(
+                egui::DragValue::new(&mut self.state.run_steps)
+                    .clamp_range(1..=100_000)
+                    .suffix(" steps"),
+            );
+            if ui.button("Run").clicked() {
+                self.sim.step(self.state.run_steps);
+            }
+        });
+
+        ui.separator();
+
+        self.render_speed(ui);
+
+        ui.separator();
+
+        self.render_behavior(ui);
+
+        ui.separator();
+
+        self.render_checkpoints(ui);
     }
 }
 
@@ -48,3 +306,18 @@ impl ToggleableUiWindow for SimulationSettingsWindow<'_> {
         egui_phosphor::regular::GEAR.to_string()
     }
 }
+
+/// A slider for a single setting, which only ends up in the patch when it is changed.
+fn patch_slider(
+    ui: &mut Ui,
+    label: &str,
+    mut value: f32,
+    range: RangeInclusive<f32>,
+    field: &mut Option<f32>,
+) {
+    ui.label(label);
+    if ui.add(Slider::new(&mut value, range)).changed() {
+        *field = Some(value);
+    }
+    ui.end_row();
+}
@@ -1,15 +1,143 @@
+use crate::ui::widgets::line_plot::{LinePlot, LineSeries};
+use crate::ui::widgets::tribe_legend::tribe_color32;
 use crate::ui::windows::{ToggleableUiWindow, UiWindow};
-use egui::{Grid, Id, Ui, WidgetText};
+use egui::{Color32, Grid, Id, Slider, Ui, Widget, WidgetText};
+use lemon_antbox_core::simulation::stats::SimulationStats;
 use lemon_antbox_core::threaded::ThreadedSimulation;
+use lemon_antbox_core::threaded::stats_history::StatsHistory;
+
+/// Longer windows are thinned out to roughly this many points per chart.
+const MAX_PLOT_POINTS: usize = 400;
+
+pub struct SimulationStatsWindowState {
+    pub is_open: bool,
+    /// How many of the most recent steps are charted.
+    window: usize,
+}
+
+impl Default for SimulationStatsWindowState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            window: 2_000,
+        }
+    }
+}
 
 pub struct SimulationStatsWindow<'a> {
-    pub is_open: &'a mut bool,
-    sim: &'a ThreadedSimulation,
+    state: &'a mut SimulationStatsWindowState,
+    sim: &'a mut ThreadedSimulation,
 }
 
 impl<'a> SimulationStatsWindow<'a> {
-    pub fn new(is_open: &'a mut bool, sim: &'a ThreadedSimulation) -> Self {
-        Self { is_open, sim }
+    pub fn new(state: &'a mut SimulationStatsWindowState, sim: &'a mut ThreadedSimulation) -> Self {
+        Self { state, sim }
+    }
+
+    fn render_charts(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Window");
+            ui.add(
+                Slider::new(&mut self.state.window, 100..=StatsHistory::DEFAULT_CAPACITY)
+                    .logarithmic(true)
+                    .suffix(" steps"),
+            );
+        });
+
+        let display = self.sim.settings().display.clone();
+        let history = self.sim.stats_history();
+        let samples = history.latest(self.state.window).collect::<Vec<_>>();
+        if samples.len() < 2 {
+            ui.small("Run the simulation to chart its stats.");
+            return;
+        }
+
+        let stride = samples.len().div_ceil(MAX_PLOT_POINTS);
+        let samples = samples.into_iter().step_by(stride).collect::<Vec<_>>();
+
+        ui.label("Ants");
+        Chart::new(&samples)
+            .series("Ants", Color32::from_rgb(165, 102, 47), |s| {
+                s.ant_count as f32
+            })
+            .series("With food", Color32::from_rgb(201, 160, 56), |s| {
+                s.ants_with_food as f32
+            })
+            .ui(ui);
+
+        ui.label("Food in the world");
+        Chart::new(&samples)
+            .series("Total food", Color32::from_rgb(120, 200, 120), |s| {
+                s.total_food as f32
+            })
+            .ui(ui);
+
+        ui.label("Food delivered");
+        let tribe_count = samples.last().map_or(0, |s| s.tribes.len());
+        let mut chart = Chart::new(&samples);
+        for tribe in 0..tribe_count {
+            chart = chart.series(
+                format!("Tribe {tribe}"),
+                tribe_color32(&display, tribe as u8),
+                move |s| s.tribes.get(tribe).map_or(0.0, |t| t.food_delivered as f32),
+            );
+        }
+        chart.ui(ui);
+
+        ui.label("Step duration");
+        Chart::new(&samples)
+            .series("ms", Color32::from_rgb(80, 160, 230), |s| {
+                s.avg_step_duration_secs * 1000.0
+            })
+            .ui(ui);
+    }
+}
+
+/// Line charts of a few values per stats sample, sharing a hover text.
+struct Chart<'a> {
+    samples: &'a [&'a SimulationStats],
+    series: Vec<(String, Color32, Vec<f32>)>,
+}
+
+impl<'a> Chart<'a> {
+    fn new(samples: &'a [&'a SimulationStats]) -> Self {
+        Self {
+            samples,
+            series: Vec::new(),
+        }
+    }
+
+    fn series(
+        mut self,
+        name: impl Into<String>,
+        color: Color32,
+        value: impl Fn(&SimulationStats) -> f32,
+    ) -> Self {
+        let values = self.samples.iter().map(|s| value(s)).collect();
+        self.series.push((name.into(), color, values));
+        self
+    }
+
+    fn ui(self, ui: &mut Ui) {
+        let series = self
+            .series
+            .iter()
+            .map(|(name, color, values)| LineSeries {
+                name,
+                color: *color,
+                values: values.clone(),
+            })
+            .collect();
+
+        LinePlot::new(series)
+            .hover_text(|index| {
+                let mut text = format!("Step {}", self.samples[index].step);
+                for (name, _, values) in &self.series {
+                    text.push_str(&format!("\n{name}: {:.2}", values[index]));
+                }
+                text
+            })
+            .ui(ui);
     }
 }
 
@@ -23,11 +151,11 @@ impl UiWindow for SimulationStatsWindow<'_> {
     }
 
     fn is_open(&self) -> bool {
-        *self.is_open
+        self.state.is_open
     }
 
     fn set_open(&mut self, open: bool) {
-        *self.is_open = open;
+        self.state.is_open = open;
     }
 
     fn render_content(&mut self, ui: &mut Ui) {
@@ -52,6 +180,9 @@ impl UiWindow for SimulationStatsWindow<'_> {
                 ui.label(format!("{:.02}ms", avg_step_duration_secs * 1000.0));
                 ui.end_row();
             });
+
+        ui.separator();
+        ui.collapsing("Charts", |ui| self.render_charts(ui));
     }
 }
 
@@ -0,0 +1,68 @@
+use crate::batch::summary::{RunMetrics, SweepSummary};
+use crate::batch::sweep::{SweepConfiguration, SweepDefinition};
+use crate::simulation::Simulation;
+use rayon::prelude::*;
+
+pub mod error;
+pub mod file;
+pub mod summary;
+pub mod sweep;
+
+/// Runs every configuration of a [`SweepDefinition`] once per seed, in parallel.
+pub struct BatchRunner<F>
+where
+    F: Fn(&mut Simulation) + Sync,
+{
+    definition: SweepDefinition,
+    setup: F,
+}
+
+impl<F> BatchRunner<F>
+where
+    F: Fn(&mut Simulation) + Sync,
+{
+    /// `setup` populates each freshly created world with nests, food and ants before it runs.
+    pub fn new(definition: SweepDefinition, setup: F) -> Self {
+        Self { definition, setup }
+    }
+
+    pub fn definition(&self) -> &SweepDefinition {
+        &self.definition
+    }
+
+    pub fn run(&self) -> Vec<SweepSummary> {
+        self.definition
+            .configurations()
+            .into_par_iter()
+            .map(|configuration| {
+                let runs = self
+                    .definition
+                    .seeds
+                    .par_iter()
+                    .map(|seed| self.run_single(&configuration, *seed))
+                    .collect();
+                SweepSummary {
+                    configuration,
+                    runs,
+                }
+            })
+            .collect()
+    }
+
+    fn run_single(&self, configuration: &SweepConfiguration, seed: u64) -> RunMetrics {
+        let mut settings = self.definition.base_settings.clone();
+        configuration.apply(&mut settings);
+        settings.seed = seed;
+        settings.paused = false;
+
+        let mut simulation = Simulation::new(settings);
+        (self.setup)(&mut simulation);
+
+        let mut metrics = RunMetrics::new(seed);
+        for _ in 0..self.definition.steps {
+            simulation.step();
+            metrics.record(simulation.stats());
+        }
+        metrics
+    }
+}
@@ -0,0 +1,60 @@
+use crate::scenario::error::ScenarioError;
+use std::fmt::Display;
+use std::io;
+
+#[derive(Debug)]
+pub enum SweepFileError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Scenario(ScenarioError),
+    Invalid { field: String, message: String },
+}
+
+impl SweepFileError {
+    pub fn invalid(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Invalid {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for SweepFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read sweep: {err}"),
+            Self::Parse(err) => write!(f, "failed to parse sweep: {err}"),
+            Self::Scenario(err) => write!(f, "invalid sweep scenario: {err}"),
+            Self::Invalid { field, message } => write!(f, "invalid `{field}`: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SweepFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+            Self::Scenario(err) => Some(err),
+            Self::Invalid { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for SweepFileError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for SweepFileError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<ScenarioError> for SweepFileError {
+    fn from(err: ScenarioError) -> Self {
+        Self::Scenario(err)
+    }
+}
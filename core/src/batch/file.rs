@@ -0,0 +1,229 @@
+use crate::batch::error::SweepFileError;
+use crate::batch::sweep::{SweepDefinition, SweepParameter, SweepParameterKind};
+use crate::batch::BatchRunner;
+use crate::scenario::error::ScenarioError;
+use crate::scenario::{validate_settings, Scenario};
+use crate::simulation::Simulation;
+use serde::Deserialize;
+use std::path::Path;
+
+/// A sweep stored as TOML: the world to run, the steps and seeds, and the swept parameters.
+///
+/// ```toml
+/// steps = 5000
+/// seeds = [0, 1, 2, 3]
+///
+/// [[parameters]]
+/// kind = "sensor_angle"
+/// values = [0.3, 0.5, 0.7]
+///
+/// [[parameters]]
+/// kind = "pheromone_decay"
+/// start = 0.9
+/// end = 0.99
+/// count = 4
+///
+/// [scenario.settings]
+/// width = 200
+/// height = 100
+///
+/// [[scenario.nests]]
+/// tribe = 0
+/// region = { type = "circle", x = 30, y = 50, radius = 3 }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SweepFile {
+    pub steps: u64,
+    #[serde(default = "default_seeds")]
+    pub seeds: Vec<u64>,
+    #[serde(default)]
+    pub parameters: Vec<SweepFileParameter>,
+    #[serde(default)]
+    pub scenario: Scenario,
+}
+
+/// Either an explicit list of `values`, or `count` values spread from `start` to `end`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "RawSweepFileParameter")]
+pub enum SweepFileParameter {
+    Values {
+        kind: SweepParameterKind,
+        values: Vec<f32>,
+    },
+    Range {
+        kind: SweepParameterKind,
+        start: f32,
+        end: f32,
+        count: usize,
+    },
+}
+
+/// The keys of both parameter forms, so a wrong or missing key can be named in the error.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSweepFileParameter {
+    kind: SweepParameterKind,
+    values: Option<Vec<f32>>,
+    start: Option<f32>,
+    end: Option<f32>,
+    count: Option<usize>,
+}
+
+impl TryFrom<RawSweepFileParameter> for SweepFileParameter {
+    type Error = String;
+
+    fn try_from(raw: RawSweepFileParameter) -> Result<Self, Self::Error> {
+        let kind = raw.kind;
+        match (raw.values, raw.start, raw.end, raw.count) {
+            (Some(values), None, None, None) => Ok(Self::Values { kind, values }),
+            (Some(_), ..) => Err(format!(
+                "`{kind}` sets `values`, it cannot also set `start`, `end` or `count`"
+            )),
+            (None, Some(start), Some(end), Some(count)) => Ok(Self::Range {
+                kind,
+                start,
+                end,
+                count,
+            }),
+            (None, None, None, None) => Err(format!(
+                "`{kind}` needs either `values`, or `start`, `end` and `count`"
+            )),
+            (None, start, end, count) => {
+                let missing = [
+                    ("start", start.is_some()),
+                    ("end", end.is_some()),
+                    ("count", count.is_some()),
+                ]
+                .into_iter()
+                .filter(|(_, set)| !set)
+                .map(|(key, _)| format!("`{key}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+                Err(format!("`{kind}` is a range and misses {missing}"))
+            }
+        }
+    }
+}
+
+fn default_seeds() -> Vec<u64> {
+    vec![0]
+}
+
+impl SweepFile {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SweepFileError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_toml_str(&content)
+    }
+
+    pub fn from_toml_str(content: &str) -> Result<Self, SweepFileError> {
+        let file: Self = toml::from_str(content)?;
+        file.validate()?;
+        Ok(file)
+    }
+
+    pub fn validate(&self) -> Result<(), SweepFileError> {
+        if self.steps == 0 {
+            return Err(SweepFileError::invalid("steps", "must be greater than 0"));
+        }
+        if self.seeds.is_empty() {
+            return Err(SweepFileError::invalid("seeds", "must not be empty"));
+        }
+        for (i, parameter) in self.parameters.iter().enumerate() {
+            if parameter.to_parameter().values.is_empty() {
+                return Err(SweepFileError::invalid(
+                    format!("parameters[{i}]"),
+                    "must sweep at least one value",
+                ));
+            }
+        }
+        self.scenario.validate()?;
+
+        for configuration in self.definition().configurations() {
+            let mut settings = self.scenario.settings.clone();
+            configuration.apply(&mut settings);
+            validate_settings(&settings).map_err(|err| match err {
+                ScenarioError::Invalid { field, message } => {
+                    let run = configuration
+                        .values
+                        .iter()
+                        .map(|(kind, value)| format!("{kind} = {value}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    SweepFileError::invalid(field, format!("{message}, in the run with {run}"))
+                }
+                err => err.into(),
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn definition(&self) -> SweepDefinition {
+        self.parameters.iter().fold(
+            SweepDefinition::new(self.scenario.settings.clone(), self.steps)
+                .seeds(self.seeds.clone()),
+            |definition, parameter| definition.parameter(parameter.to_parameter()),
+        )
+    }
+
+    /// A runner that populates every run with the file's scenario.
+    pub fn runner(&self) -> BatchRunner<impl Fn(&mut Simulation) + Sync + '_> {
+        BatchRunner::new(self.definition(), |simulation| {
+            simulation.populate(&self.scenario)
+        })
+    }
+}
+
+impl SweepFileParameter {
+    pub fn to_parameter(&self) -> SweepParameter {
+        match self {
+            Self::Values { kind, values } => SweepParameter::values(*kind, values.clone()),
+            Self::Range {
+                kind,
+                start,
+                end,
+                count,
+            } => SweepParameter::range(*kind, *start, *end, *count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(parameter: &str) -> Result<SweepFile, SweepFileError> {
+        SweepFile::from_toml_str(&format!("steps = 10\n\n[[parameters]]\n{parameter}"))
+    }
+
+    #[test]
+    fn both_parameter_forms_are_read() {
+        let file = parse("kind = \"speed\"\nvalues = [0.5, 1.0]").unwrap();
+        assert_eq!(file.parameters[0].to_parameter().values, [0.5, 1.0]);
+
+        let file = parse("kind = \"speed\"\nstart = 0.5\nend = 1.5\ncount = 3").unwrap();
+        assert_eq!(file.parameters[0].to_parameter().values, [0.5, 1.0, 1.5]);
+    }
+
+    #[test]
+    fn bad_parameter_keys_are_named() {
+        let err = parse("kind = \"speed\"\nvalue = [1.0]").unwrap_err();
+        assert!(err.to_string().contains("`value`"), "{err}");
+
+        let err = parse("kind = \"speed\"\nstart = 0.5\ncount = 3").unwrap_err();
+        assert!(err.to_string().contains("misses `end`"), "{err}");
+
+        let err = parse("kind = \"speed\"\nvalues = [1.0]\ncount = 3").unwrap_err();
+        assert!(err.to_string().contains("cannot also set"), "{err}");
+    }
+
+    #[test]
+    fn runs_with_invalid_settings_are_rejected() {
+        let err = parse("kind = \"pheromone_decay\"\nvalues = [0.5, 1.5]").unwrap_err();
+        assert!(
+            matches!(&err, SweepFileError::Invalid { field, .. } if field == "settings.pheromone_decay"),
+            "{err}"
+        );
+        assert!(err.to_string().contains("pheromone_decay = 1.5"), "{err}");
+    }
+}
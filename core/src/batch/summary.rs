@@ -0,0 +1,125 @@
+use crate::batch::sweep::SweepConfiguration;
+use crate::simulation::stats::SimulationStats;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Foraging metrics of a single run, tracked step by step.
+#[derive(Debug, Default, Clone)]
+pub struct RunMetrics {
+    pub seed: u64,
+    pub steps: u64,
+    pub food_delivered: u64,
+    pub first_delivery_step: Option<u64>,
+    pub remaining_food: u64,
+    pub mean_ants_with_food: f32,
+}
+
+impl RunMetrics {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Default::default()
+        }
+    }
+
+    pub fn record(&mut self, stats: &SimulationStats) {
+        self.steps += 1;
+        self.food_delivered = stats.tribes.iter().map(|t| t.food_delivered).sum();
+        if self.first_delivery_step.is_none() && self.food_delivered > 0 {
+            self.first_delivery_step = Some(stats.step);
+        }
+        self.remaining_food = stats.total_food;
+        self.mean_ants_with_food +=
+            (stats.ants_with_food as f32 - self.mean_ants_with_food) / self.steps as f32;
+    }
+}
+
+/// Metrics of one configuration, aggregated over all seeds.
+#[derive(Debug, Clone)]
+pub struct SweepSummary {
+    pub configuration: SweepConfiguration,
+    pub runs: Vec<RunMetrics>,
+}
+
+impl SweepSummary {
+    pub fn mean_food_delivered(&self) -> f32 {
+        self.mean_of(|run| Some(run.food_delivered as f32))
+            .unwrap_or_default()
+    }
+
+    pub fn std_food_delivered(&self) -> f32 {
+        let mean = self.mean_food_delivered();
+        self.mean_of(|run| Some((run.food_delivered as f32 - mean).powi(2)))
+            .unwrap_or_default()
+            .sqrt()
+    }
+
+    /// Mean step of the first delivery, over the runs that delivered anything at all.
+    pub fn mean_first_delivery_step(&self) -> Option<f32> {
+        self.mean_of(|run| run.first_delivery_step.map(|step| step as f32))
+    }
+
+    pub fn delivering_runs(&self) -> usize {
+        self.runs
+            .iter()
+            .filter(|run| run.first_delivery_step.is_some())
+            .count()
+    }
+
+    pub fn mean_remaining_food(&self) -> f32 {
+        self.mean_of(|run| Some(run.remaining_food as f32))
+            .unwrap_or_default()
+    }
+
+    pub fn mean_ants_with_food(&self) -> f32 {
+        self.mean_of(|run| Some(run.mean_ants_with_food))
+            .unwrap_or_default()
+    }
+
+    fn mean_of(&self, value: impl Fn(&RunMetrics) -> Option<f32>) -> Option<f32> {
+        let values = self.runs.iter().filter_map(value).collect::<Vec<_>>();
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.iter().sum::<f32>() / values.len() as f32)
+        }
+    }
+}
+
+/// Writes one row per configuration, with the swept parameters as leading columns.
+pub fn write_summary_csv(summaries: &[SweepSummary], path: impl AsRef<Path>) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    if let Some(first) = summaries.first() {
+        for (kind, _) in &first.configuration.values {
+            write!(writer, "{kind},")?;
+        }
+    }
+    writeln!(
+        writer,
+        "runs,mean_food_delivered,std_food_delivered,delivering_runs,mean_first_delivery_step,mean_remaining_food,mean_ants_with_food"
+    )?;
+
+    for summary in summaries {
+        for (_, value) in &summary.configuration.values {
+            write!(writer, "{value},")?;
+        }
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{}",
+            summary.runs.len(),
+            summary.mean_food_delivered(),
+            summary.std_food_delivered(),
+            summary.delivering_runs(),
+            summary
+                .mean_first_delivery_step()
+                .map(|step| step.to_string())
+                .unwrap_or_default(),
+            summary.mean_remaining_food(),
+            summary.mean_ants_with_food()
+        )?;
+    }
+
+    writer.flush()
+}
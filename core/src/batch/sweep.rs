@@ -0,0 +1,142 @@
+use crate::simulation::settings::SimulationSettings;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SweepParameterKind {
+    SensorAngle,
+    SensorDistance,
+    Speed,
+    TurnAngle,
+    WobbleStrength,
+    AntPheromoneStrength,
+    PheromoneDecay,
+    PheromoneDiffusion,
+    NestPheromoneStrength,
+}
+
+impl SweepParameterKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SensorAngle => "sensor_angle",
+            Self::SensorDistance => "sensor_distance",
+            Self::Speed => "speed",
+            Self::TurnAngle => "turn_angle",
+            Self::WobbleStrength => "wobble_strength",
+            Self::AntPheromoneStrength => "ant_pheromone_strength",
+            Self::PheromoneDecay => "pheromone_decay",
+            Self::PheromoneDiffusion => "pheromone_diffusion",
+            Self::NestPheromoneStrength => "nest_pheromone_strength",
+        }
+    }
+
+    pub fn apply(&self, settings: &mut SimulationSettings, value: f32) {
+        match self {
+            Self::SensorAngle => settings.ant.sensor_angle = value,
+            Self::SensorDistance => settings.ant.sensor_distance = value,
+            Self::Speed => settings.ant.speed = value,
+            Self::TurnAngle => settings.ant.turn_angle = value,
+            Self::WobbleStrength => settings.ant.wobble_strength = value,
+            Self::AntPheromoneStrength => settings.ant.pheromone_strength = value,
+            Self::PheromoneDecay => settings.pheromone_decay = value,
+            Self::PheromoneDiffusion => settings.pheromone_diffusion = value,
+            Self::NestPheromoneStrength => settings.nest_pheromone_strength = value,
+        }
+    }
+}
+
+impl Display for SweepParameterKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SweepParameter {
+    pub kind: SweepParameterKind,
+    pub values: Vec<f32>,
+}
+
+impl SweepParameter {
+    pub fn values(kind: SweepParameterKind, values: impl Into<Vec<f32>>) -> Self {
+        Self {
+            kind,
+            values: values.into(),
+        }
+    }
+
+    /// `count` evenly spaced values from `start` to `end`, both inclusive.
+    pub fn range(kind: SweepParameterKind, start: f32, end: f32, count: usize) -> Self {
+        let values = match count {
+            0 => Vec::new(),
+            1 => vec![start],
+            _ => (0..count)
+                .map(|i| start + (end - start) * i as f32 / (count - 1) as f32)
+                .collect(),
+        };
+        Self { kind, values }
+    }
+}
+
+/// A single point in the parameter space, one value per swept parameter.
+#[derive(Debug, Clone)]
+pub struct SweepConfiguration {
+    pub values: Vec<(SweepParameterKind, f32)>,
+}
+
+impl SweepConfiguration {
+    pub fn apply(&self, settings: &mut SimulationSettings) {
+        for (kind, value) in &self.values {
+            kind.apply(settings, *value);
+        }
+    }
+}
+
+pub struct SweepDefinition {
+    pub base_settings: SimulationSettings,
+    pub parameters: Vec<SweepParameter>,
+    pub seeds: Vec<u64>,
+    pub steps: u64,
+}
+
+impl SweepDefinition {
+    pub fn new(base_settings: SimulationSettings, steps: u64) -> Self {
+        Self {
+            base_settings,
+            parameters: Vec::new(),
+            seeds: vec![0],
+            steps,
+        }
+    }
+
+    pub fn parameter(mut self, parameter: SweepParameter) -> Self {
+        self.parameters.push(parameter);
+        self
+    }
+
+    pub fn seeds(mut self, seeds: impl Into<Vec<u64>>) -> Self {
+        self.seeds = seeds.into();
+        self
+    }
+
+    /// The cartesian product of all parameter values.
+    pub fn configurations(&self) -> Vec<SweepConfiguration> {
+        let mut configurations = vec![SweepConfiguration { values: Vec::new() }];
+
+        for parameter in &self.parameters {
+            configurations = configurations
+                .iter()
+                .flat_map(|configuration| {
+                    parameter.values.iter().map(|value| {
+                        let mut values = configuration.values.clone();
+                        values.push((parameter.kind, *value));
+                        SweepConfiguration { values }
+                    })
+                })
+                .collect();
+        }
+
+        configurations
+    }
+}
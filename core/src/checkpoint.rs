@@ -0,0 +1,191 @@
+use crate::simulation::Simulation;
+use crate::simulation::snapshot::SimulationSnapshot;
+use std::collections::VecDeque;
+use std::io;
+
+#[derive(Debug, Clone)]
+pub struct CheckpointSettings {
+    /// A checkpoint is taken every n-th step, 0 disables checkpoints.
+    pub interval: u64,
+    /// The oldest checkpoints are dropped once the compressed ring exceeds this size.
+    pub memory_budget_bytes: usize,
+}
+
+impl Default for CheckpointSettings {
+    fn default() -> Self {
+        Self {
+            interval: 250,
+            memory_budget_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+struct Checkpoint {
+    step: u64,
+    data: Vec<u8>,
+}
+
+/// Compressed snapshots of the past, oldest first.
+pub struct CheckpointRing {
+    settings: CheckpointSettings,
+    checkpoints: VecDeque<Checkpoint>,
+    used_bytes: usize,
+}
+
+impl CheckpointRing {
+    pub fn new(settings: CheckpointSettings) -> Self {
+        Self {
+            settings,
+            checkpoints: VecDeque::new(),
+            used_bytes: 0,
+        }
+    }
+
+    pub fn settings(&self) -> &CheckpointSettings {
+        &self.settings
+    }
+
+    pub fn set_settings(&mut self, settings: CheckpointSettings) {
+        self.settings = settings;
+        self.enforce_budget();
+    }
+
+    pub fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    pub fn oldest_step(&self) -> Option<u64> {
+        self.checkpoints.front().map(|c| c.step)
+    }
+
+    /// Takes a checkpoint if the simulation's current step is due for one.
+    pub fn maybe_capture(&mut self, simulation: &Simulation) -> io::Result<bool> {
+        let step = simulation.stats().step;
+        if self.settings.interval == 0
+            || !step.is_multiple_of(self.settings.interval)
+            || self.checkpoints.back().is_some_and(|c| c.step >= step)
+        {
+            return Ok(false);
+        }
+
+        self.capture(simulation)?;
+        Ok(true)
+    }
+
+    pub fn capture(&mut self, simulation: &Simulation) -> io::Result<()> {
+        let data = simulation.snapshot().to_compressed_bytes()?;
+        self.used_bytes += data.len();
+        self.checkpoints.push_back(Checkpoint {
+            step: simulation.stats().step,
+            data,
+        });
+        self.enforce_budget();
+        Ok(())
+    }
+
+    /// The most recent snapshot taken at or before `step`, `None` if all are newer than that.
+    pub fn latest_at_or_before(&self, step: u64) -> io::Result<Option<SimulationSnapshot>> {
+        let checkpoint = self.checkpoints.iter().rev().find(|c| c.step <= step);
+
+        checkpoint
+            .map(|c| SimulationSnapshot::from_compressed_bytes(&c.data))
+            .transpose()
+    }
+
+    /// Drops all checkpoints after `step`, they describe a future that will not happen anymore.
+    pub fn truncate_after(&mut self, step: u64) {
+        while let Some(checkpoint) = self.checkpoints.back()
+            && checkpoint.step > step
+        {
+            self.used_bytes -= checkpoint.data.len();
+            self.checkpoints.pop_back();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.checkpoints.clear();
+        self.used_bytes = 0;
+    }
+
+    fn enforce_budget(&mut self) {
+        while self.used_bytes > self.settings.memory_budget_bytes
+            && let Some(checkpoint) = self.checkpoints.pop_front()
+        {
+            self.used_bytes -= checkpoint.data.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::fixture::{world, world_bytes};
+
+    fn ring(interval: u64) -> CheckpointRing {
+        CheckpointRing::new(CheckpointSettings {
+            interval,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn rewinding_to_a_checkpoint_continues_the_same_run() {
+        let mut simulation = world(5);
+        let mut checkpoints = ring(10);
+        let mut at_step_15 = None;
+
+        for _ in 0..30 {
+            simulation.step();
+            checkpoints.maybe_capture(&simulation).unwrap();
+            if simulation.stats().step == 15 {
+                at_step_15 = Some(world_bytes(&simulation));
+            }
+        }
+        assert_eq!(checkpoints.len(), 3);
+
+        let snapshot = checkpoints.latest_at_or_before(15).unwrap().unwrap();
+        assert_eq!(snapshot.step(), 10);
+
+        let mut rewound = Simulation::from_snapshot(snapshot);
+        while rewound.stats().step < 15 {
+            rewound.step();
+        }
+        assert_eq!(Some(world_bytes(&rewound)), at_step_15);
+    }
+
+    #[test]
+    fn no_checkpoint_before_the_oldest_one() {
+        let mut simulation = world(5);
+        let mut checkpoints = ring(10);
+        for _ in 0..25 {
+            simulation.step();
+            checkpoints.maybe_capture(&simulation).unwrap();
+        }
+
+        assert_eq!(checkpoints.oldest_step(), Some(10));
+        assert!(checkpoints.latest_at_or_before(9).unwrap().is_none());
+    }
+
+    #[test]
+    fn truncate_drops_the_future() {
+        let mut simulation = world(5);
+        let mut checkpoints = ring(5);
+        for _ in 0..20 {
+            simulation.step();
+            checkpoints.maybe_capture(&simulation).unwrap();
+        }
+
+        checkpoints.truncate_after(12);
+        assert_eq!(checkpoints.len(), 2);
+        let snapshot = checkpoints.latest_at_or_before(20).unwrap().unwrap();
+        assert_eq!(snapshot.step(), 10);
+    }
+}
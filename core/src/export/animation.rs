@@ -0,0 +1,229 @@
+use crate::export::frame::{encode_png, upscale_nearest};
+use crate::simulation::Simulation;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufWriter, Cursor};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use strum_macros::EnumIter;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, EnumIter)]
+pub enum AnimationFormat {
+    #[default]
+    Gif,
+    Apng,
+}
+
+impl AnimationFormat {
+    /// Guesses the format from the file extension (`.gif`, `.png` or `.apng`).
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "gif" => Some(Self::Gif),
+            "png" | "apng" => Some(Self::Apng),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Gif => "gif",
+            Self::Apng => "png",
+        }
+    }
+}
+
+impl Display for AnimationFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gif => write!(f, "GIF"),
+            Self::Apng => write!(f, "APNG"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimationSettings {
+    /// Only every n-th simulation step becomes a frame.
+    pub every_n_steps: u64,
+    /// Playback speed of the resulting animation.
+    pub frames_per_second: u16,
+    pub scale: u32,
+    /// Recording stops on its own once the animation reaches this length.
+    pub max_duration: Duration,
+    /// GIF palette quantization speed from 1 (best quality) to 30 (fastest).
+    pub quantization_speed: i32,
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self {
+            every_n_steps: 2,
+            frames_per_second: 30,
+            scale: 2,
+            max_duration: Duration::from_secs(20),
+            quantization_speed: 10,
+        }
+    }
+}
+
+impl AnimationSettings {
+    pub fn max_frames(&self) -> u32 {
+        (self.max_duration.as_secs_f64() * self.frames_per_second.max(1) as f64).ceil() as u32
+    }
+}
+
+enum AnimationEncoder {
+    Gif(gif::Encoder<BufWriter<File>>),
+    // APNG needs the frame count up front, so frames are kept as compressed PNGs until the end
+    // and the file is only created once there is something to write
+    Apng(Vec<Vec<u8>>),
+}
+
+/// Records every n-th step of a run into an animated GIF or APNG.
+pub struct AnimationRecorder {
+    path: PathBuf,
+    encoder: AnimationEncoder,
+    settings: AnimationSettings,
+    width: u32,
+    height: u32,
+    frame_count: u32,
+    buffer: Vec<u8>,
+}
+
+impl AnimationRecorder {
+    /// Creates the GIF output file right away, `width` and `height` are the world size before
+    /// scaling.
+    pub fn create(
+        path: impl Into<PathBuf>,
+        format: AnimationFormat,
+        settings: AnimationSettings,
+        width: u16,
+        height: u16,
+    ) -> io::Result<Self> {
+        let path = path.into();
+        let scale = settings.scale.max(1);
+        let width = width as u32 * scale;
+        let height = height as u32 * scale;
+
+        let encoder = match format {
+            AnimationFormat::Gif => {
+                if width > u16::MAX as u32 || height > u16::MAX as u32 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("{width}x{height} is too large for a GIF"),
+                    ));
+                }
+                let writer = BufWriter::new(File::create(&path)?);
+                let mut encoder = gif::Encoder::new(writer, width as u16, height as u16, &[])
+                    .map_err(io::Error::other)?;
+                encoder
+                    .set_repeat(gif::Repeat::Infinite)
+                    .map_err(io::Error::other)?;
+                AnimationEncoder::Gif(encoder)
+            }
+            AnimationFormat::Apng => AnimationEncoder::Apng(Vec::new()),
+        };
+
+        Ok(Self {
+            path,
+            encoder,
+            settings: AnimationSettings { scale, ..settings },
+            width,
+            height,
+            frame_count: 0,
+            buffer: Vec::new(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// Whether the maximum duration is reached and no further frames will be recorded.
+    pub fn is_full(&self) -> bool {
+        self.frame_count >= self.settings.max_frames()
+    }
+
+    /// Adds the current state as a frame if the step is due, returns whether a frame was added.
+    pub fn record(&mut self, simulation: &Simulation) -> io::Result<bool> {
+        if self.is_full()
+            || !simulation
+                .stats()
+                .step
+                .is_multiple_of(self.settings.every_n_steps.max(1))
+        {
+            return Ok(false);
+        }
+
+        let settings = simulation.settings();
+        self.buffer.resize(settings.cell_count() * 4, 0);
+        simulation.draw(&mut self.buffer);
+        let mut frame = upscale_nearest(
+            &self.buffer,
+            settings.width as u32,
+            settings.height as u32,
+            self.settings.scale,
+        );
+
+        match &mut self.encoder {
+            AnimationEncoder::Gif(encoder) => {
+                let speed = self.settings.quantization_speed.clamp(1, 30);
+                let mut gif_frame = gif::Frame::from_rgba_speed(
+                    self.width as u16,
+                    self.height as u16,
+                    &mut frame,
+                    speed,
+                );
+                // GIF delays are in hundredths of a second
+                gif_frame.delay = (100 / self.settings.frames_per_second.max(1)).max(1);
+                encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+            }
+            AnimationEncoder::Apng(frames) => {
+                let mut compressed = Vec::new();
+                encode_png(&mut compressed, &frame, self.width, self.height)?;
+                frames.push(compressed);
+            }
+        }
+
+        self.frame_count += 1;
+        Ok(true)
+    }
+
+    /// Completes the file, an APNG without any frames is never written.
+    pub fn finish(self) -> io::Result<()> {
+        match self.encoder {
+            AnimationEncoder::Gif(encoder) => {
+                encoder.into_inner().map_err(io::Error::other)?;
+                Ok(())
+            }
+            AnimationEncoder::Apng(frames) => {
+                if frames.is_empty() {
+                    return Ok(());
+                }
+
+                let writer = BufWriter::new(File::create(&self.path)?);
+                let mut encoder = png::Encoder::new(writer, self.width, self.height);
+                encoder.set_color(png::ColorType::Rgba);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder.set_animated(frames.len() as u32, 0)?;
+                encoder.set_frame_delay(1, self.settings.frames_per_second.max(1))?;
+
+                let mut writer = encoder.write_header()?;
+                let mut pixels = Vec::new();
+                for compressed in frames {
+                    let mut reader = png::Decoder::new(Cursor::new(compressed)).read_info()?;
+                    pixels.resize(reader.output_buffer_size().unwrap_or_default(), 0);
+                    reader.next_frame(&mut pixels)?;
+                    writer.write_image_data(&pixels)?;
+                }
+                writer.finish()?;
+                Ok(())
+            }
+        }
+    }
+}
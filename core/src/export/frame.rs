@@ -0,0 +1,121 @@
+use crate::simulation::Simulation;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Scales an RGBA frame up by an integer factor, repeating each pixel.
+pub fn upscale_nearest(frame: &[u8], width: u32, height: u32, scale: u32) -> Vec<u8> {
+    let scale = scale.max(1) as usize;
+    let (width, height) = (width as usize, height as usize);
+    if scale == 1 {
+        return frame.to_vec();
+    }
+
+    let row_len = width * scale * 4;
+    let mut scaled = Vec::with_capacity(row_len * height * scale);
+    for row in frame.chunks_exact(width * 4).take(height) {
+        let start = scaled.len();
+        for pixel in row.chunks_exact(4) {
+            for _ in 0..scale {
+                scaled.extend_from_slice(pixel);
+            }
+        }
+        for _ in 1..scale {
+            scaled.extend_from_within(start..start + row_len);
+        }
+    }
+    scaled
+}
+
+pub fn encode_png(writer: impl Write, frame: &[u8], width: u32, height: u32) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()?
+        .write_image_data(frame)
+        .map_err(io::Error::other)
+}
+
+/// Writes an RGBA frame as produced by [`Simulation::draw`] to a PNG file.
+pub fn save_png(
+    path: impl AsRef<Path>,
+    frame: &[u8],
+    width: u32,
+    height: u32,
+    scale: u32,
+) -> io::Result<()> {
+    let scale = scale.max(1);
+    let frame = upscale_nearest(frame, width, height, scale);
+    let writer = BufWriter::new(File::create(path)?);
+    encode_png(writer, &frame, width * scale, height * scale)
+}
+
+pub fn save_screenshot(
+    simulation: &Simulation,
+    path: impl AsRef<Path>,
+    scale: u32,
+) -> io::Result<()> {
+    let settings = simulation.settings();
+    let mut frame = vec![0; settings.cell_count() * 4];
+    simulation.draw(&mut frame);
+    save_png(
+        path,
+        &frame,
+        settings.width as u32,
+        settings.height as u32,
+        scale,
+    )
+}
+
+/// Writes every n-th step as a numbered PNG into a directory, for stitching into time-lapses.
+pub struct FrameRecorder {
+    directory: PathBuf,
+    every_n_steps: u64,
+    scale: u32,
+    frame_index: u32,
+    buffer: Vec<u8>,
+}
+
+impl FrameRecorder {
+    pub fn new(directory: impl Into<PathBuf>, every_n_steps: u64, scale: u32) -> io::Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory,
+            every_n_steps: every_n_steps.max(1),
+            scale: scale.max(1),
+            frame_index: 0,
+            buffer: Vec::new(),
+        })
+    }
+
+    pub fn frames_written(&self) -> u32 {
+        self.frame_index
+    }
+
+    /// Writes the next frame if the current step is due, returns whether a frame was written.
+    pub fn record(&mut self, simulation: &Simulation) -> io::Result<bool> {
+        if !simulation.stats().step.is_multiple_of(self.every_n_steps) {
+            return Ok(false);
+        }
+
+        let settings = simulation.settings();
+        self.buffer.resize(settings.cell_count() * 4, 0);
+        simulation.draw(&mut self.buffer);
+
+        let path = self
+            .directory
+            .join(format!("frame_{:06}.png", self.frame_index));
+        save_png(
+            path,
+            &self.buffer,
+            settings.width as u32,
+            settings.height as u32,
+            self.scale,
+        )?;
+
+        self.frame_index += 1;
+        Ok(true)
+    }
+}
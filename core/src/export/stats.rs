@@ -0,0 +1,106 @@
+use crate::simulation::stats::SimulationStats;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StatsFormat {
+    Csv,
+    JsonLines,
+}
+
+impl StatsFormat {
+    /// Guesses the format from the file extension (`.csv`, `.jsonl` or `.ndjson`).
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "jsonl" | "ndjson" => Some(Self::JsonLines),
+            _ => None,
+        }
+    }
+}
+
+/// Streams one row of [`SimulationStats`] per step into a CSV or JSON Lines file.
+pub struct StatsExporter {
+    writer: BufWriter<File>,
+    format: StatsFormat,
+    header_written: bool,
+}
+
+impl StatsExporter {
+    pub fn create(path: impl AsRef<Path>, format: StatsFormat) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            format,
+            header_written: false,
+        })
+    }
+
+    /// Creates an exporter, picking the format from the file extension.
+    pub fn create_from_path(path: impl AsRef<Path>) -> io::Result<Self> {
+        let format = StatsFormat::from_path(&path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unknown stats export format, expected .csv or .jsonl",
+            )
+        })?;
+        Self::create(path, format)
+    }
+
+    pub fn format(&self) -> StatsFormat {
+        self.format
+    }
+
+    pub fn write(&mut self, stats: &SimulationStats) -> io::Result<()> {
+        match self.format {
+            StatsFormat::Csv => self.write_csv(stats),
+            StatsFormat::JsonLines => self.write_json_line(stats),
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn write_csv(&mut self, stats: &SimulationStats) -> io::Result<()> {
+        if !self.header_written {
+            write!(
+                self.writer,
+                "step,ant_count,ants_with_food,total_food,avg_step_duration_secs"
+            )?;
+            for tribe in 0..stats.tribes.len() {
+                write!(
+                    self.writer,
+                    ",tribe_{tribe}_ant_count,tribe_{tribe}_ants_with_food,tribe_{tribe}_food_delivered"
+                )?;
+            }
+            writeln!(self.writer)?;
+            self.header_written = true;
+        }
+
+        write!(
+            self.writer,
+            "{},{},{},{},{}",
+            stats.step,
+            stats.ant_count,
+            stats.ants_with_food,
+            stats.total_food,
+            stats.avg_step_duration_secs
+        )?;
+        for tribe in &stats.tribes {
+            write!(
+                self.writer,
+                ",{},{},{}",
+                tribe.ant_count, tribe.ants_with_food, tribe.food_delivered
+            )?;
+        }
+        writeln!(self.writer)
+    }
+
+    fn write_json_line(&mut self, stats: &SimulationStats) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, stats)?;
+        writeln!(self.writer)
+    }
+}
@@ -0,0 +1,98 @@
+use crate::export::animation::AnimationRecorder;
+use crate::export::frame::FrameRecorder;
+use crate::export::stats::StatsExporter;
+use crate::simulation::Simulation;
+use std::io;
+
+/// Drives a [`Simulation`] on the current thread without any rendering or frame pacing.
+pub struct HeadlessRunner {
+    simulation: Simulation,
+    stats_exporter: Option<StatsExporter>,
+    frame_recorder: Option<FrameRecorder>,
+    animation_recorder: Option<AnimationRecorder>,
+}
+
+impl HeadlessRunner {
+    pub fn new(simulation: Simulation) -> Self {
+        Self {
+            simulation,
+            stats_exporter: None,
+            frame_recorder: None,
+            animation_recorder: None,
+        }
+    }
+
+    pub fn with_stats_exporter(mut self, exporter: StatsExporter) -> Self {
+        self.stats_exporter = Some(exporter);
+        self
+    }
+
+    pub fn with_frame_recorder(mut self, recorder: FrameRecorder) -> Self {
+        self.frame_recorder = Some(recorder);
+        self
+    }
+
+    pub fn with_animation_recorder(mut self, recorder: AnimationRecorder) -> Self {
+        self.animation_recorder = Some(recorder);
+        self
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    pub fn simulation_mut(&mut self) -> &mut Simulation {
+        &mut self.simulation
+    }
+
+    pub fn into_simulation(self) -> Simulation {
+        self.simulation
+    }
+
+    pub fn step(&mut self) -> io::Result<()> {
+        let previous_step = self.simulation.stats().step;
+        self.simulation.step();
+        if self.simulation.stats().step == previous_step {
+            return Ok(());
+        }
+
+        if let Some(exporter) = &mut self.stats_exporter {
+            exporter.write(self.simulation.stats())?;
+        }
+
+        if let Some(recorder) = &mut self.frame_recorder {
+            recorder.record(&self.simulation)?;
+        }
+
+        if let Some(recorder) = &mut self.animation_recorder {
+            recorder.record(&self.simulation)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn run(&mut self, steps: u64) -> io::Result<()> {
+        for _ in 0..steps {
+            self.step()?;
+        }
+
+        if let Some(exporter) = &mut self.stats_exporter {
+            exporter.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Flushes all exports and completes a running animation, returning the simulation.
+    pub fn finish(mut self) -> io::Result<Simulation> {
+        if let Some(exporter) = &mut self.stats_exporter {
+            exporter.flush()?;
+        }
+
+        if let Some(recorder) = self.animation_recorder.take() {
+            recorder.finish()?;
+        }
+
+        Ok(self.simulation)
+    }
+}
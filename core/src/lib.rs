@@ -1,4 +1,13 @@
+pub mod batch;
+pub mod checkpoint;
+pub mod export;
+pub mod headless;
+pub mod map_import;
+pub mod preset;
+pub mod replay;
+pub mod scenario;
 pub mod simulation;
 #[cfg(feature = "threaded")]
 pub mod threaded;
 mod utils;
+pub mod worldgen;
@@ -0,0 +1,285 @@
+//! Building worlds from PNG images painted in any image editor.
+//!
+//! Every pixel becomes one cell, the color legend is:
+//!
+//! | Color                                | Cell                                  |
+//! |--------------------------------------|---------------------------------------|
+//! | black `#000000` or alpha below 128   | empty ground                          |
+//! | gray, red = green = blue > 0         | wall                                  |
+//! | green `#00XX00`                      | food, the green channel is the amount |
+//! | blue `#XX00FF`                       | nest, the red channel is the tribe    |
+//!
+//! Any other color is rejected with the pixel coordinates, so typos in the palette are easy to find.
+
+use crate::map_import::error::MapImportError;
+use crate::simulation::Simulation;
+use crate::simulation::settings::SimulationSettings;
+use png::{ColorType, Decoder, Transformations};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek};
+use std::path::Path;
+
+pub mod error;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MapPixel {
+    Empty,
+    Wall,
+    Food(u8),
+    Nest(u8),
+}
+
+impl MapPixel {
+    pub fn from_rgba(rgba: [u8; 4]) -> Option<Self> {
+        let [r, g, b, a] = rgba;
+        if a < 128 || (r == 0 && g == 0 && b == 0) {
+            Some(Self::Empty)
+        } else if r == g && g == b {
+            Some(Self::Wall)
+        } else if r == 0 && b == 0 {
+            Some(Self::Food(g))
+        } else if g == 0 && b == 255 {
+            Some(Self::Nest(r))
+        } else {
+            None
+        }
+    }
+}
+
+/// A decoded map, one [`MapPixel`] per cell in row-major order.
+pub struct MapImage {
+    width: u16,
+    height: u16,
+    pixels: Vec<MapPixel>,
+}
+
+impl MapImage {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, MapImportError> {
+        Self::decode(BufReader::new(File::open(path)?))
+    }
+
+    pub fn decode(reader: impl BufRead + Seek) -> Result<Self, MapImportError> {
+        let mut decoder = Decoder::new(reader);
+        decoder.set_transformations(Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+
+        let mut buffer = vec![0; reader.output_buffer_size().unwrap_or_default()];
+        let info = reader.next_frame(&mut buffer)?;
+        if info.width > u16::MAX as u32 || info.height > u16::MAX as u32 {
+            return Err(MapImportError::TooLarge {
+                width: info.width,
+                height: info.height,
+            });
+        }
+
+        let channels = info.color_type.samples();
+        let mut pixels = Vec::with_capacity(info.width as usize * info.height as usize);
+        for y in 0..info.height {
+            let line = &buffer[y as usize * info.line_size..][..info.width as usize * channels];
+            for (x, pixel) in line.chunks_exact(channels).enumerate() {
+                let rgba = match info.color_type {
+                    ColorType::Grayscale => [pixel[0], pixel[0], pixel[0], 255],
+                    ColorType::GrayscaleAlpha => [pixel[0], pixel[0], pixel[0], pixel[1]],
+                    ColorType::Rgb => [pixel[0], pixel[1], pixel[2], 255],
+                    ColorType::Rgba => [pixel[0], pixel[1], pixel[2], pixel[3]],
+                    // Palettes are expanded by the decoder transformations
+                    ColorType::Indexed => unreachable!(),
+                };
+                let map_pixel = MapPixel::from_rgba(rgba).ok_or(MapImportError::UnknownColor {
+                    x: x as u32,
+                    y,
+                    rgba,
+                })?;
+                pixels.push(map_pixel);
+            }
+        }
+
+        Ok(Self {
+            width: info.width as u16,
+            height: info.height as u16,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn get(&self, x: u16, y: u16) -> Option<MapPixel> {
+        self.pixels
+            .get(y as usize * self.width as usize + x as usize)
+            .copied()
+    }
+
+    /// Builds a world of the image's size, overriding `width` and `height` of the given settings.
+    pub fn build(&self, settings: SimulationSettings) -> Result<Simulation, MapImportError> {
+        let settings = SimulationSettings {
+            width: self.width,
+            height: self.height,
+            ..settings
+        };
+
+        for (i, pixel) in self.pixels.iter().enumerate() {
+            if let MapPixel::Nest(tribe) = pixel
+                && *tribe >= settings.tribe_count
+            {
+                return Err(MapImportError::UnknownTribe {
+                    x: (i % self.width as usize) as u32,
+                    y: (i / self.width as usize) as u32,
+                    tribe: *tribe,
+                    tribe_count: settings.tribe_count,
+                });
+            }
+        }
+
+        let mut simulation = Simulation::new(settings);
+        for (i, pixel) in self.pixels.iter().enumerate() {
+            let x = (i % self.width as usize) as u16;
+            let y = (i / self.width as usize) as u16;
+            match pixel {
+                MapPixel::Empty => {}
+                MapPixel::Wall => simulation.spawn_wall(x, y),
+                MapPixel::Food(amount) => simulation.spawn_food(x, y, *amount),
+                MapPixel::Nest(tribe) => simulation.spawn_nest(x, y, *tribe),
+            }
+        }
+
+        Ok(simulation)
+    }
+}
+
+impl Simulation {
+    /// Loads a map image and builds a world sized to it, see the [module docs](crate::map_import) for the legend.
+    pub fn from_map_image(
+        path: impl AsRef<Path>,
+        settings: SimulationSettings,
+    ) -> Result<Self, MapImportError> {
+        MapImage::open(path)?.build(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use png::Encoder;
+    use std::io::Cursor;
+
+    fn encode(width: u32, height: u32, color_type: ColorType, data: &[u8]) -> Cursor<Vec<u8>> {
+        let mut bytes = Vec::new();
+        let mut encoder = Encoder::new(&mut bytes, width, height);
+        encoder.set_color(color_type);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(data).unwrap();
+        writer.finish().unwrap();
+        Cursor::new(bytes)
+    }
+
+    fn rgba_map(pixels: &[[u8; 4]], width: u32) -> Cursor<Vec<u8>> {
+        encode(
+            width,
+            pixels.len() as u32 / width,
+            ColorType::Rgba,
+            pixels.as_flattened(),
+        )
+    }
+
+    #[test]
+    fn legend_colors_map_to_cells() {
+        let cases = [
+            ([0, 0, 0, 255], Some(MapPixel::Empty)),
+            ([200, 40, 90, 100], Some(MapPixel::Empty)),
+            ([128, 128, 128, 255], Some(MapPixel::Wall)),
+            ([0, 60, 0, 255], Some(MapPixel::Food(60))),
+            ([1, 0, 255, 255], Some(MapPixel::Nest(1))),
+            ([255, 0, 0, 255], None),
+            ([10, 0, 200, 255], None),
+        ];
+        for (rgba, pixel) in cases {
+            assert_eq!(MapPixel::from_rgba(rgba), pixel, "{rgba:?}");
+        }
+    }
+
+    #[test]
+    fn built_world_follows_the_image() {
+        let pixels = [
+            [0, 0, 0, 255],
+            [255, 255, 255, 255],
+            [0, 80, 0, 255],
+            [1, 0, 255, 255],
+        ];
+        let map = MapImage::decode(rgba_map(&pixels, 2)).unwrap();
+        assert_eq!((map.width(), map.height()), (2, 2));
+
+        let simulation = map.build(SimulationSettings::default()).unwrap();
+        assert_eq!(
+            (simulation.settings().width, simulation.settings().height),
+            (2, 2)
+        );
+        let ground = simulation.get_cell(0, 0).unwrap();
+        assert!(!ground.flags.has_wall() && !ground.flags.has_home() && ground.food == 0);
+        assert!(simulation.get_cell(1, 0).unwrap().flags.has_wall());
+        assert_eq!(simulation.get_cell(0, 1).unwrap().food, 80);
+        let nest = simulation.get_cell(1, 1).unwrap();
+        assert!(nest.flags.has_home());
+        assert_eq!(nest.tribe, 1);
+    }
+
+    #[test]
+    fn unknown_colors_are_reported_with_their_position() {
+        let pixels = [
+            [0, 0, 0, 255],
+            [0, 0, 0, 255],
+            [0, 0, 0, 255],
+            [255, 0, 0, 255],
+        ];
+        let err = MapImage::decode(rgba_map(&pixels, 2)).err().unwrap();
+        assert!(matches!(
+            err,
+            MapImportError::UnknownColor {
+                x: 1,
+                y: 1,
+                rgba: [255, 0, 0, 255]
+            }
+        ));
+    }
+
+    #[test]
+    fn nests_of_missing_tribes_are_rejected() {
+        let pixels = [[3, 0, 255, 255]];
+        let map = MapImage::decode(rgba_map(&pixels, 1)).unwrap();
+        let settings = SimulationSettings {
+            tribe_count: 2,
+            ..Default::default()
+        };
+        let err = map.build(settings).err().unwrap();
+        assert!(matches!(
+            err,
+            MapImportError::UnknownTribe {
+                x: 0,
+                y: 0,
+                tribe: 3,
+                tribe_count: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn images_wider_than_a_world_are_rejected() {
+        let width = u16::MAX as u32 + 1;
+        let data = vec![0; width as usize];
+        let err = MapImage::decode(encode(width, 1, ColorType::Grayscale, &data))
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            MapImportError::TooLarge {
+                width: 65536,
+                height: 1
+            }
+        ));
+    }
+}
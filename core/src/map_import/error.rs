@@ -0,0 +1,74 @@
+use std::fmt::Display;
+use std::io;
+
+#[derive(Debug)]
+pub enum MapImportError {
+    Io(io::Error),
+    Decode(png::DecodingError),
+    TooLarge {
+        width: u32,
+        height: u32,
+    },
+    UnknownColor {
+        x: u32,
+        y: u32,
+        rgba: [u8; 4],
+    },
+    UnknownTribe {
+        x: u32,
+        y: u32,
+        tribe: u8,
+        tribe_count: u8,
+    },
+}
+
+impl Display for MapImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read map image: {err}"),
+            Self::Decode(err) => write!(f, "failed to decode map image: {err}"),
+            Self::TooLarge { width, height } => write!(
+                f,
+                "map image is {width}x{height}, at most {}x{} is supported",
+                u16::MAX,
+                u16::MAX
+            ),
+            Self::UnknownColor { x, y, rgba } => write!(
+                f,
+                "pixel ({x}, {y}) has color #{:02x}{:02x}{:02x}{:02x} which is not part of the map legend",
+                rgba[0], rgba[1], rgba[2], rgba[3]
+            ),
+            Self::UnknownTribe {
+                x,
+                y,
+                tribe,
+                tribe_count,
+            } => write!(
+                f,
+                "pixel ({x}, {y}) places a nest of tribe {tribe}, but there are only {tribe_count} tribes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MapImportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MapImportError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<png::DecodingError> for MapImportError {
+    fn from(err: png::DecodingError) -> Self {
+        Self::Decode(err)
+    }
+}
@@ -0,0 +1,146 @@
+use crate::preset::error::PresetError;
+use crate::scenario::error::ScenarioError;
+use crate::scenario::validate_settings;
+use crate::simulation::settings::SimulationSettings;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub mod error;
+
+const EXTENSION: &str = "toml";
+/// Settings describing the current session rather than a configuration, never stored in or
+/// restored from presets.
+const SESSION_FIELDS: [&str; 3] = ["seed", "paused", "fast_forward"];
+
+/// Named [`SimulationSettings`], each stored as `<name>.toml` in a directory.
+pub struct PresetStore {
+    directory: PathBuf,
+}
+
+impl PresetStore {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// The names of all stored presets, sorted alphabetically.
+    pub fn list(&self) -> io::Result<Vec<String>> {
+        let entries = match std::fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut names = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == EXTENSION)
+                && let Some(name) = path.file_stem().and_then(|stem| stem.to_str())
+            {
+                names.push(name.to_string());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.path(name).is_ok_and(|path| path.is_file())
+    }
+
+    pub fn load(&self, name: &str) -> Result<SimulationSettings, PresetError> {
+        let content = std::fs::read_to_string(self.path(name)?)?;
+        let mut table: toml::Table = toml::from_str(&content)?;
+        for field in SESSION_FIELDS {
+            table.remove(field);
+        }
+        let settings: SimulationSettings = toml::Value::Table(table).try_into()?;
+        validate_settings(&settings).map_err(|err| match err {
+            // Presets are the bare settings, without the scenario's `settings` table
+            ScenarioError::Invalid { field, message } => PresetError::Invalid {
+                field: field.trim_start_matches("settings.").to_string(),
+                message,
+            },
+            ScenarioError::Io(err) => PresetError::Io(err),
+            ScenarioError::Parse(err) => PresetError::Parse(err),
+        })?;
+        Ok(settings)
+    }
+
+    /// Stores `settings` under `name`, replacing an existing preset of the same name.
+    pub fn save(&self, name: &str, settings: &SimulationSettings) -> Result<(), PresetError> {
+        let path = self.path(name)?;
+        std::fs::create_dir_all(&self.directory)?;
+        // Reset before serializing, TOML integers cannot hold every seed
+        let settings = SimulationSettings {
+            seed: 0,
+            paused: false,
+            fast_forward: false,
+            ..settings.clone()
+        };
+        let mut table = toml::Table::try_from(&settings)?;
+        for field in SESSION_FIELDS {
+            table.remove(field);
+        }
+        let content = toml::to_string_pretty(&table)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn delete(&self, name: &str) -> Result<(), PresetError> {
+        std::fs::remove_file(self.path(name)?)?;
+        Ok(())
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf, PresetError> {
+        let valid = !name.trim().is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'));
+        if !valid {
+            return Err(PresetError::InvalidName(name.to_string()));
+        }
+
+        Ok(self.directory.join(name).with_extension(EXTENSION))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_leave_out_the_session() {
+        let directory = std::env::temp_dir().join(format!("antbox-presets-{}", std::process::id()));
+        let store = PresetStore::new(&directory);
+        let settings = SimulationSettings {
+            // Beyond what TOML integers can hold
+            seed: u64::MAX - 5,
+            paused: true,
+            fast_forward: true,
+            pheromone_decay: 0.5,
+            ..Default::default()
+        };
+
+        store.save("test", &settings).unwrap();
+        let content = std::fs::read_to_string(directory.join("test.toml")).unwrap();
+        let loaded = store.load("test");
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        for field in SESSION_FIELDS {
+            assert!(
+                !content.lines().any(|line| line.starts_with(field)),
+                "{field}"
+            );
+        }
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.pheromone_decay, 0.5);
+        assert!(!loaded.paused);
+        assert!(!loaded.fast_forward);
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt::Display;
+use std::io;
+
+#[derive(Debug)]
+pub enum PresetError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Serialize(toml::ser::Error),
+    InvalidName(String),
+    Invalid { field: String, message: String },
+}
+
+impl Display for PresetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to access preset: {err}"),
+            Self::Parse(err) => write!(f, "failed to parse preset: {err}"),
+            Self::Serialize(err) => write!(f, "failed to serialize preset: {err}"),
+            Self::InvalidName(name) => write!(
+                f,
+                "invalid preset name `{name}`: only letters, digits, spaces, `-` and `_` are allowed"
+            ),
+            Self::Invalid { field, message } => write!(f, "invalid `{field}`: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for PresetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+            Self::Serialize(err) => Some(err),
+            Self::InvalidName(_) | Self::Invalid { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for PresetError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for PresetError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<toml::ser::Error> for PresetError {
+    fn from(err: toml::ser::Error) -> Self {
+        Self::Serialize(err)
+    }
+}
@@ -0,0 +1,367 @@
+use crate::simulation::Simulation;
+use crate::simulation::group::AntGroupEdit;
+use crate::simulation::region::{Region, RegionEdit};
+use crate::simulation::revert::Revert;
+use crate::simulation::settings::SettingsPatch;
+use crate::simulation::snapshot::SimulationSnapshot;
+use crate::utils::compression::{compress, decompress};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"ANTRPLY5";
+
+/// Everything that changes a running world from the outside.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReplayAction {
+    Clear,
+    SpawnAnt {
+        x: u16,
+        y: u16,
+        tribe: u8,
+    },
+    SpawnNest {
+        x: u16,
+        y: u16,
+        tribe: u8,
+    },
+    SpawnFood {
+        x: u16,
+        y: u16,
+        amount: u8,
+    },
+    UpdateSettings(SettingsPatch),
+    EditRegion {
+        #[serde(with = "stored_region")]
+        region: Region,
+        edit: RegionEdit,
+    },
+    EditAnts {
+        ants: Vec<usize>,
+        edit: AntGroupEdit,
+    },
+    /// Undoes an earlier world edit.
+    Revert(Box<Revert>),
+}
+
+impl ReplayAction {
+    /// Applies the action, returning how to undo it if it edited the world.
+    pub fn apply_reversible(&self, simulation: &mut Simulation) -> Option<Revert> {
+        let revert = match self {
+            Self::Clear => Some(Revert::capture_all(simulation)),
+            Self::SpawnAnt { x, y, .. }
+            | Self::SpawnNest { x, y, .. }
+            | Self::SpawnFood { x, y, .. } => Some(Revert::capture(
+                simulation,
+                &Region::Point { x: *x, y: *y },
+                false,
+            )),
+            Self::EditRegion { region, edit } => Some(Revert::capture(
+                simulation,
+                region,
+                matches!(edit, RegionEdit::Erase | RegionEdit::Wall),
+            )),
+            Self::EditAnts { ants, edit } => Some(Revert::capture_ants(
+                simulation,
+                ants,
+                *edit == AntGroupEdit::Delete,
+            )),
+            Self::UpdateSettings(_) | Self::Revert(_) => None,
+        };
+
+        self.apply(simulation);
+        revert
+    }
+
+    pub fn apply(&self, simulation: &mut Simulation) {
+        match self {
+            Self::Clear => simulation.clear(),
+            Self::SpawnAnt { x, y, tribe } => simulation.spawn_ant(*x, *y, *tribe),
+            Self::SpawnNest { x, y, tribe } => simulation.spawn_nest(*x, *y, *tribe),
+            Self::SpawnFood { x, y, amount } => simulation.spawn_food(*x, *y, *amount),
+            Self::UpdateSettings(patch) => patch.apply(simulation.settings_mut()),
+            Self::EditRegion { region, edit } => simulation.edit_region(region, *edit),
+            Self::EditAnts { ants, edit } => simulation.edit_ants(ants, *edit),
+            Self::Revert(revert) => simulation.revert(revert),
+        }
+    }
+}
+
+/// Postcard cannot read the internally tagged [`Region`] used by scenarios, so replays store
+/// regions externally tagged instead.
+mod stored_region {
+    use crate::simulation::region::Region;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    enum StoredRegion {
+        Point {
+            x: u16,
+            y: u16,
+        },
+        Circle {
+            x: u16,
+            y: u16,
+            radius: u16,
+        },
+        Rectangle {
+            x: u16,
+            y: u16,
+            width: u16,
+            height: u16,
+        },
+        Line {
+            from_x: u16,
+            from_y: u16,
+            to_x: u16,
+            to_y: u16,
+            radius: u16,
+        },
+    }
+
+    pub fn serialize<S: Serializer>(region: &Region, serializer: S) -> Result<S::Ok, S::Error> {
+        let stored = match *region {
+            Region::Point { x, y } => StoredRegion::Point { x, y },
+            Region::Circle { x, y, radius } => StoredRegion::Circle { x, y, radius },
+            Region::Rectangle {
+                x,
+                y,
+                width,
+                height,
+            } => StoredRegion::Rectangle {
+                x,
+                y,
+                width,
+                height,
+            },
+            Region::Line {
+                from_x,
+                from_y,
+                to_x,
+                to_y,
+                radius,
+            } => StoredRegion::Line {
+                from_x,
+                from_y,
+                to_x,
+                to_y,
+                radius,
+            },
+        };
+        stored.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Region, D::Error> {
+        Ok(match StoredRegion::deserialize(deserializer)? {
+            StoredRegion::Point { x, y } => Region::Point { x, y },
+            StoredRegion::Circle { x, y, radius } => Region::Circle { x, y, radius },
+            StoredRegion::Rectangle {
+                x,
+                y,
+                width,
+                height,
+            } => Region::Rectangle {
+                x,
+                y,
+                width,
+                height,
+            },
+            StoredRegion::Line {
+                from_x,
+                from_y,
+                to_x,
+                to_y,
+                radius,
+            } => Region::Line {
+                from_x,
+                from_y,
+                to_x,
+                to_y,
+                radius,
+            },
+        })
+    }
+}
+
+/// An action applied right before the simulation advanced past `step`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub step: u64,
+    pub action: ReplayAction,
+}
+
+/// A starting state plus every action applied to it, enough to reproduce a session exactly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayLog {
+    pub initial: SimulationSnapshot,
+    pub events: Vec<ReplayEvent>,
+    pub end_step: u64,
+}
+
+impl ReplayLog {
+    pub fn new(initial: SimulationSnapshot) -> Self {
+        Self {
+            end_step: initial.step(),
+            initial,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, step: u64, action: ReplayAction) {
+        self.events.push(ReplayEvent { step, action });
+        self.end_step = self.end_step.max(step);
+    }
+
+    pub fn set_end_step(&mut self, step: u64) {
+        self.end_step = step;
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(compress(self)?);
+        std::fs::write(path, bytes)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let Some(compressed) = bytes.strip_prefix(MAGIC) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a replay file",
+            ));
+        };
+        decompress(compressed)
+    }
+
+    /// Drops all events at or after `step`, used when the simulation is rewound.
+    pub fn truncate(&mut self, step: u64) {
+        self.events.retain(|event| event.step < step);
+        self.end_step = self.end_step.min(step);
+    }
+
+    pub fn player(&self) -> ReplayPlayer {
+        self.player_from(0)
+    }
+
+    /// A player that skips all events before `step`.
+    pub fn player_from(&self, step: u64) -> ReplayPlayer {
+        ReplayPlayer {
+            events: self.events.clone(),
+            next_event: self.events.partition_point(|event| event.step < step),
+        }
+    }
+
+    /// Plays the whole log against a fresh simulation restored from the initial state.
+    pub fn replay(&self) -> Simulation {
+        let mut simulation = Simulation::from_snapshot(self.initial.clone());
+        simulation.settings_mut().paused = false;
+
+        let mut player = self.player();
+        while simulation.stats().step < self.end_step {
+            player.apply_due(&mut simulation);
+            simulation.step();
+        }
+        player.apply_due(&mut simulation);
+
+        simulation
+    }
+}
+
+/// Feeds the events of a log into a simulation as it reaches their steps.
+pub struct ReplayPlayer {
+    events: Vec<ReplayEvent>,
+    next_event: usize,
+}
+
+impl ReplayPlayer {
+    /// Removes and returns all actions due at the simulation's current step.
+    pub fn take_due(&mut self, step: u64) -> Vec<ReplayAction> {
+        let mut due = Vec::new();
+        while let Some(event) = self.events.get(self.next_event)
+            && event.step <= step
+        {
+            due.push(event.action.clone());
+            self.next_event += 1;
+        }
+        due
+    }
+
+    pub fn apply_due(&mut self, simulation: &mut Simulation) {
+        for action in self.take_due(simulation.stats().step) {
+            action.apply(simulation);
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_event >= self.events.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::fixture::{world, world_bytes};
+
+    #[test]
+    fn saved_log_replays_the_session_exactly() {
+        let mut simulation = world(3);
+        let mut log = ReplayLog::new(simulation.snapshot());
+        let events = [
+            (
+                3,
+                ReplayAction::SpawnAnt {
+                    x: 30,
+                    y: 20,
+                    tribe: 1,
+                },
+            ),
+            (
+                5,
+                ReplayAction::EditRegion {
+                    region: Region::Circle {
+                        x: 40,
+                        y: 30,
+                        radius: 4,
+                    },
+                    edit: RegionEdit::Food { amount: 50 },
+                },
+            ),
+            (
+                8,
+                ReplayAction::EditAnts {
+                    ants: vec![0, 2],
+                    edit: AntGroupEdit::Delete,
+                },
+            ),
+        ];
+
+        for step in 0..12 {
+            for (_, action) in events.iter().filter(|(at, _)| *at == step) {
+                action.apply(&mut simulation);
+                log.record(step, action.clone());
+            }
+            simulation.step();
+        }
+        log.set_end_step(simulation.stats().step);
+
+        let path = std::env::temp_dir().join(format!("antbox-replay-{}.bin", std::process::id()));
+        log.save(&path).unwrap();
+        let loaded = ReplayLog::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let replayed = loaded.unwrap().replay();
+        assert_eq!(replayed.stats().step, simulation.stats().step);
+        assert_eq!(world_bytes(&replayed), world_bytes(&simulation));
+    }
+
+    #[test]
+    fn load_rejects_other_files() {
+        let path =
+            std::env::temp_dir().join(format!("antbox-not-a-replay-{}.bin", std::process::id()));
+        std::fs::write(&path, b"ANTRPLY0 something else").unwrap();
+        let loaded = ReplayLog::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
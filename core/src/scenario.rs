@@ -0,0 +1,278 @@
+use crate::scenario::error::ScenarioError;
+use crate::simulation::Simulation;
+use crate::simulation::region::Region;
+use crate::simulation::settings::SimulationSettings;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub mod error;
+
+/// A human-editable description of a world, stored as TOML.
+///
+/// ```toml
+/// [settings]
+/// width = 200
+/// height = 100
+/// tribe_count = 1
+///
+/// [[nests]]
+/// tribe = 0
+/// region = { type = "circle", x = 30, y = 50, radius = 3 }
+///
+/// [[food]]
+/// amount = 100
+/// region = { type = "rectangle", x = 150, y = 40, width = 10, height = 20 }
+///
+/// [[walls]]
+/// region = { type = "rectangle", x = 90, y = 0, width = 4, height = 70 }
+///
+/// [[ants]]
+/// tribe = 0
+/// count = 200
+/// region = { type = "point", x = 30, y = 50 }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scenario {
+    pub settings: SimulationSettings,
+    pub nests: Vec<ScenarioNest>,
+    pub food: Vec<ScenarioFood>,
+    pub walls: Vec<ScenarioWall>,
+    pub ants: Vec<ScenarioAnts>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioNest {
+    pub tribe: u8,
+    pub region: Region,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioFood {
+    pub amount: u8,
+    pub region: Region,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioWall {
+    pub region: Region,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioAnts {
+    pub tribe: u8,
+    pub count: u16,
+    pub region: Region,
+}
+
+impl Scenario {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScenarioError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_toml_str(&content)
+    }
+
+    pub fn from_toml_str(content: &str) -> Result<Self, ScenarioError> {
+        let scenario: Self = toml::from_str(content)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ScenarioError> {
+        std::fs::write(path, self.to_toml_string())?;
+        Ok(())
+    }
+
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string_pretty(self).expect("scenarios always serialize to TOML")
+    }
+
+    pub fn validate(&self) -> Result<(), ScenarioError> {
+        validate_settings(&self.settings)?;
+
+        for (i, nest) in self.nests.iter().enumerate() {
+            self.validate_tribe(&format!("nests[{i}].tribe"), nest.tribe)?;
+            self.validate_region(&format!("nests[{i}].region"), &nest.region)?;
+        }
+
+        for (i, food) in self.food.iter().enumerate() {
+            if food.amount == 0 {
+                return Err(ScenarioError::invalid(
+                    format!("food[{i}].amount"),
+                    "must be greater than 0",
+                ));
+            }
+            self.validate_region(&format!("food[{i}].region"), &food.region)?;
+        }
+
+        for (i, wall) in self.walls.iter().enumerate() {
+            self.validate_region(&format!("walls[{i}].region"), &wall.region)?;
+        }
+
+        let mut total_ants = 0u32;
+        for (i, ants) in self.ants.iter().enumerate() {
+            self.validate_tribe(&format!("ants[{i}].tribe"), ants.tribe)?;
+            self.validate_region(&format!("ants[{i}].region"), &ants.region)?;
+            total_ants += ants.count as u32;
+            if total_ants > u16::MAX as u32 {
+                return Err(ScenarioError::invalid(
+                    format!("ants[{i}].count"),
+                    format!("a world holds at most {} ants", u16::MAX),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_tribe(&self, field: &str, tribe: u8) -> Result<(), ScenarioError> {
+        if tribe >= self.settings.tribe_count {
+            return Err(ScenarioError::invalid(
+                field,
+                format!(
+                    "tribe {tribe} does not exist, settings.tribe_count is {}",
+                    self.settings.tribe_count
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_region(&self, field: &str, region: &Region) -> Result<(), ScenarioError> {
+        if let Region::Rectangle { width, height, .. } = region
+            && (*width == 0 || *height == 0)
+        {
+            return Err(ScenarioError::invalid(
+                field,
+                "rectangle width and height must be greater than 0",
+            ));
+        }
+
+        if region
+            .cells(self.settings.width, self.settings.height)
+            .is_empty()
+        {
+            return Err(ScenarioError::invalid(
+                field,
+                format!(
+                    "region lies outside of the {}x{} grid",
+                    self.settings.width, self.settings.height
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn validate_settings(settings: &SimulationSettings) -> Result<(), ScenarioError> {
+    let checks = [
+        (
+            "settings.width",
+            settings.width > 0,
+            "must be greater than 0",
+        ),
+        (
+            "settings.height",
+            settings.height > 0,
+            "must be greater than 0",
+        ),
+        (
+            "settings.tribe_count",
+            settings.tribe_count > 0,
+            "must be greater than 0",
+        ),
+        (
+            "settings.steps_per_second",
+            settings.steps_per_second > 0,
+            "must be greater than 0",
+        ),
+        (
+            "settings.speed_multiplier",
+            settings.speed_multiplier > 0,
+            "must be greater than 0",
+        ),
+        (
+            "settings.pheromone_decay",
+            (0.0..=1.0).contains(&settings.pheromone_decay),
+            "must be between 0 and 1",
+        ),
+        (
+            "settings.pheromone_diffusion",
+            (0.0..=1.0).contains(&settings.pheromone_diffusion),
+            "must be between 0 and 1",
+        ),
+        (
+            "settings.nest_pheromone_strength",
+            settings.nest_pheromone_strength >= 0.0,
+            "must not be negative",
+        ),
+        (
+            "settings.display.max_heat",
+            settings.display.max_heat > 0.0,
+            "must be greater than 0",
+        ),
+        (
+            "settings.ant.pheromone_strength",
+            settings.ant.pheromone_strength >= 0.0,
+            "must not be negative",
+        ),
+        (
+            "settings.ant.sensor_distance",
+            settings.ant.sensor_distance >= 0.0,
+            "must not be negative",
+        ),
+        (
+            "settings.ant.speed",
+            settings.ant.speed > 0.0,
+            "must be greater than 0",
+        ),
+        (
+            "settings.ant.wobble_strength",
+            settings.ant.wobble_strength >= 0.0,
+            "must not be negative",
+        ),
+        (
+            "settings.ant.spiral_expansion_rate",
+            settings.ant.spiral_expansion_rate >= 0.0,
+            "must not be negative",
+        ),
+    ];
+
+    for (field, valid, message) in checks {
+        if !valid {
+            return Err(ScenarioError::invalid(field, message));
+        }
+    }
+    Ok(())
+}
+
+impl Simulation {
+    /// Builds a populated world from a scenario, validating it first.
+    pub fn from_scenario(scenario: &Scenario) -> Result<Self, ScenarioError> {
+        scenario.validate()?;
+
+        let mut simulation = Simulation::new(scenario.settings.clone());
+        simulation.populate(scenario);
+
+        Ok(simulation)
+    }
+
+    /// Spawns the walls, nests, food and ants of an already validated scenario.
+    pub fn populate(&mut self, scenario: &Scenario) {
+        for wall in &scenario.walls {
+            self.spawn_wall_region(&wall.region);
+        }
+        for nest in &scenario.nests {
+            self.spawn_nest_region(&nest.region, nest.tribe);
+        }
+        for food in &scenario.food {
+            self.spawn_food_region(&food.region, food.amount);
+        }
+        for ants in &scenario.ants {
+            self.spawn_ants_in_region(&ants.region, ants.tribe, ants.count);
+        }
+    }
+}
@@ -1,45 +1,96 @@
 use crate::simulation::ant::{Ant, AntAction, AntFeedback, AntSenses};
 use crate::simulation::cell::Cell;
+use crate::simulation::display::{Colormap, RenderLayer};
 use crate::simulation::pheromones::{PheromoneType, Pheromones};
+use crate::simulation::region::{Region, RegionEdit};
 use crate::simulation::settings::SimulationSettings;
-use crate::simulation::stats::SimulationStats;
+use crate::simulation::snapshot::SimulationSnapshot;
+use crate::simulation::stats::{SimulationStats, TribeStats};
 use crate::utils::color::alpha_blend;
-use rayon::iter::ParallelIterator;
+use fastrand::Rng;
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 use rayon::prelude::IntoParallelRefIterator;
 use std::time::Instant;
 
 pub mod ant;
 pub mod cell;
+pub mod display;
+pub mod food;
+pub mod group;
 pub mod pheromones;
+pub mod region;
+pub mod revert;
 pub mod settings;
+pub mod snapshot;
 pub mod stats;
 
+const FOOD_OUTLINE_COLOR: [u8; 4] = [230, 255, 200, 255];
+const SELECTED_ANT_COLOR: [u8; 4] = [90, 220, 255, 255];
+
 pub struct Simulation {
     ants: Vec<Ant>,
     cells: Vec<Cell>,
     pheromones: Pheromones,
     settings: SimulationSettings,
     stats: SimulationStats,
+    rng: Rng,
+    /// Ant indices in ascending order, reset whenever ants are removed.
+    selected_ants: Vec<usize>,
 }
 
 impl Simulation {
     pub fn new(settings: SimulationSettings) -> Self {
         let cells = vec![Cell::default(); settings.cell_count()];
+        let stats = SimulationStats {
+            tribes: vec![TribeStats::default(); settings.tribe_count as usize],
+            ..Default::default()
+        };
         Self {
             ants: Vec::new(),
             cells,
             pheromones: Pheromones::new(settings.width, settings.height, settings.tribe_count),
+            rng: Rng::with_seed(settings.seed),
             settings,
-            stats: SimulationStats::default(),
+            stats,
+            selected_ants: Vec::new(),
         }
     }
 
     pub fn clear(&mut self) {
         self.ants.clear();
+        self.on_ant_indices_shifted();
         self.cells = vec![Cell::default(); self.settings.cell_count()];
         self.pheromones.clear();
     }
 
+    pub fn snapshot(&self) -> SimulationSnapshot {
+        SimulationSnapshot {
+            settings: self.settings.clone(),
+            ants: self.ants.clone(),
+            cells: self.cells.clone(),
+            pheromones: self.pheromones.layers().to_vec(),
+            stats: self.stats.clone(),
+            rng_state: self.rng.get_seed(),
+        }
+    }
+
+    pub fn from_snapshot(snapshot: SimulationSnapshot) -> Self {
+        let settings = snapshot.settings;
+        Self {
+            ants: snapshot.ants,
+            cells: snapshot.cells,
+            pheromones: Pheromones::from_layers(
+                settings.width,
+                settings.height,
+                snapshot.pheromones,
+            ),
+            stats: snapshot.stats,
+            rng: Rng::with_seed(snapshot.rng_state),
+            settings,
+            selected_ants: Vec::new(),
+        }
+    }
+
     pub fn settings(&self) -> &SimulationSettings {
         &self.settings
     }
@@ -56,6 +107,12 @@ impl Simulation {
         self.ants.len() as u16
     }
 
+    /// Forgets about ants referenced by index, after ants were removed or inserted in between.
+    fn on_ant_indices_shifted(&mut self) {
+        self.settings.inspected_ant = None;
+        self.selected_ants.clear();
+    }
+
     fn coords_to_index(&self, x: u16, y: u16) -> usize {
         y as usize * self.settings.width as usize + x as usize
     }
@@ -71,6 +128,7 @@ impl Simulation {
             || y >= self.settings.height
             || self.ants.len() > 65535
             || tribe >= self.settings.tribe_count
+            || self.is_wall(x, y)
         {
             return;
         }
@@ -78,7 +136,7 @@ impl Simulation {
         let ant = Ant {
             x: x as f32,
             y: y as f32,
-            angle: fastrand::f32() * std::f32::consts::PI * 2.0,
+            angle: self.rng.f32() * std::f32::consts::PI * 2.0,
             tribe,
             ..Default::default()
         };
@@ -94,12 +152,16 @@ impl Simulation {
         }
 
         let index = self.coords_to_index(x, y);
+        if self.cells[index].flags.has_wall() {
+            return;
+        }
+
         self.cells[index].tribe = tribe;
         self.cells[index].flags.set_home(true);
     }
 
     pub fn spawn_food(&mut self, x: u16, y: u16, amount: u8) {
-        if x >= self.settings.width || y >= self.settings.height {
+        if x >= self.settings.width || y >= self.settings.height || self.is_wall(x, y) {
             return;
         }
 
@@ -107,6 +169,103 @@ impl Simulation {
         self.cells[index].food = self.cells[index].food.saturating_add(amount);
     }
 
+    /// Turns the cell into an obstacle, removing any food, nest or ants on it.
+    pub fn spawn_wall(&mut self, x: u16, y: u16) {
+        if x >= self.settings.width || y >= self.settings.height {
+            return;
+        }
+
+        self.place_wall(x, y);
+        self.remove_ants_in_region(&Region::Point { x, y });
+    }
+
+    fn place_wall(&mut self, x: u16, y: u16) {
+        let index = self.coords_to_index(x, y);
+        self.cells[index] = Cell::default();
+        self.cells[index].flags.set_wall(true);
+    }
+
+    /// Removes the ants standing in the region, they could never walk out of a wall.
+    fn remove_ants_in_region(&mut self, region: &Region) {
+        let ant_count = self.ants.len();
+        self.ants
+            .retain(|ant| !region.contains(ant.x as i32, ant.y as i32));
+        if self.ants.len() != ant_count {
+            self.on_ant_indices_shifted();
+        }
+    }
+
+    /// Spawns `count` ants on randomly picked cells of the region.
+    pub fn spawn_ants_in_region(&mut self, region: &Region, tribe: u8, count: u16) {
+        let cells = self.free_region_cells(region);
+        if cells.is_empty() {
+            return;
+        }
+
+        for _ in 0..count {
+            let (x, y) = cells[self.rng.usize(..cells.len())];
+            self.spawn_ant(x, y, tribe);
+        }
+    }
+
+    pub fn spawn_nest_region(&mut self, region: &Region, tribe: u8) {
+        for (x, y) in region.cells(self.settings.width, self.settings.height) {
+            self.spawn_nest(x, y, tribe);
+        }
+    }
+
+    pub fn spawn_food_region(&mut self, region: &Region, amount: u8) {
+        for (x, y) in region.cells(self.settings.width, self.settings.height) {
+            self.spawn_food(x, y, amount);
+        }
+    }
+
+    pub fn spawn_wall_region(&mut self, region: &Region) {
+        for (x, y) in region.cells(self.settings.width, self.settings.height) {
+            self.place_wall(x, y);
+        }
+        self.remove_ants_in_region(region);
+    }
+
+    /// Removes ants, nests, food and walls from the region, leaving the pheromones.
+    pub fn erase_region(&mut self, region: &Region) {
+        self.remove_ants_in_region(region);
+
+        for (x, y) in region.cells(self.settings.width, self.settings.height) {
+            let index = self.coords_to_index(x, y);
+            self.cells[index] = Cell::default();
+        }
+    }
+
+    pub fn edit_region(&mut self, region: &Region, edit: RegionEdit) {
+        match edit {
+            RegionEdit::Ants { tribe, density } => {
+                // The fractional part becomes a chance for one more ant, so sparse brushes still work
+                let expected = self.free_region_cells(region).len() as f32 * density.max(0.0);
+                let extra = self.rng.f32() < expected.fract();
+                let count = (expected as u32 + extra as u32).min(u16::MAX as u32) as u16;
+                self.spawn_ants_in_region(region, tribe, count);
+            }
+            RegionEdit::Nest { tribe } => self.spawn_nest_region(region, tribe),
+            RegionEdit::Food { amount } => self.spawn_food_region(region, amount),
+            RegionEdit::Wall => self.spawn_wall_region(region),
+            RegionEdit::Erase => self.erase_region(region),
+        }
+    }
+
+    fn free_region_cells(&self, region: &Region) -> Vec<(u16, u16)> {
+        region
+            .cells(self.settings.width, self.settings.height)
+            .into_iter()
+            .filter(|(x, y)| !self.is_wall(*x, *y))
+            .collect()
+    }
+
+    pub fn is_wall(&self, x: u16, y: u16) -> bool {
+        self.get_cell(x, y)
+            .is_some_and(|cell| cell.flags.has_wall())
+    }
+
     pub fn get_cell(&self, x: u16, y: u16) -> Option<Cell> {
         let index = self.coords_to_index(x, y);
         self.cells.get(index).copied()
@@ -136,46 +295,122 @@ impl Simulation {
 // Draw
 impl Simulation {
     pub fn draw(&self, frame: &mut [u8]) {
-        self.draw_cells(frame);
-        self.draw_pheromones(frame);
-        self.draw_ants(frame);
+        let display = &self.settings.display;
+
+        self.draw_ground(frame);
+        for layer in display.render_layers() {
+            match layer {
+                RenderLayer::Nests if display.show_nests => self.draw_nests(frame),
+                RenderLayer::Food if display.show_food => self.draw_food(frame),
+                RenderLayer::Pheromones => self.draw_pheromones(frame),
+                RenderLayer::Ants if display.show_ants => self.draw_ants(frame),
+                _ => {}
+            }
+        }
     }
 
     fn draw_ants(&self, frame: &mut [u8]) {
         for (i, ant) in self.ants.iter().enumerate() {
             let index = self.coords_to_index(ant.x as u16, ant.y as u16) * 4;
             let inspected = self.settings.inspected_ant == Some(i as u16);
-            frame[index..index + 4].copy_from_slice(&ant.color_rgba(inspected));
+            let color = if !inspected && self.selected_ants.binary_search(&i).is_ok() {
+                SELECTED_ANT_COLOR
+            } else {
+                ant.color_rgba(self.settings.display.tribe_color(ant.tribe), inspected)
+            };
+            frame[index..index + 4].copy_from_slice(&color);
         }
     }
 
     fn draw_pheromones(&self, frame: &mut [u8]) {
-        if self.settings.drawn_pheromone_tribe >= self.settings.tribe_count {
-            return;
+        let display = &self.settings.display;
+
+        if let Some(pheromone) = self.settings.drawn_pheromone
+            && display.pheromone_tribe < self.settings.tribe_count
+        {
+            let tribe = display.pheromone_tribe;
+            let layer = self.pheromones.get_layer(tribe, pheromone);
+            let tint = display.tribe_color(tribe);
+            self.draw_pheromone_layer(frame, layer, display.colormap, tint);
         }
 
-        let Some(pheromone) = self.settings.drawn_pheromone else {
-            return;
+        for overlay in &display.overlay_layers {
+            if !overlay.hidden && overlay.tribe < self.settings.tribe_count {
+                let layer = self.pheromones.get_layer(overlay.tribe, overlay.pheromone);
+                self.draw_pheromone_layer(frame, layer, Colormap::Tint, overlay.tint);
+            }
+        }
+    }
+
+    fn draw_pheromone_layer(
+        &self,
+        frame: &mut [u8],
+        layer: &[f32],
+        colormap: Colormap,
+        tint: [u8; 3],
+    ) {
+        let display = &self.settings.display;
+        let max_heat = if display.auto_max_heat {
+            layer.iter().copied().fold(0.0, f32::max)
+        } else {
+            display.max_heat
         };
+        if max_heat <= 0.0 {
+            return;
+        }
 
-        for (value, pixel) in self
-            .pheromones
-            .get_layer(self.settings.drawn_pheromone_tribe, pheromone)
-            .iter()
-            .zip(frame.chunks_exact_mut(4))
-        {
-            let alpha = ((*value / self.settings.drawn_pheromone_max_heat) * 255.0) as u8;
-            let color = [255, 0, 0, alpha];
+        for (value, pixel) in layer.iter().zip(frame.chunks_exact_mut(4)) {
+            let t = display.heat_scale.normalize(*value, max_heat);
+            let color = colormap.color(t, tint);
             let final_color = alpha_blend(color, pixel.try_into().unwrap());
             pixel.copy_from_slice(&final_color);
         }
     }
 
-    fn draw_cells(&self, frame: &mut [u8]) {
+    fn draw_ground(&self, frame: &mut [u8]) {
+        for (cell, pixel) in self.cells.iter().zip(frame.chunks_exact_mut(4)) {
+            pixel.copy_from_slice(&cell.ground_color_rgba());
+        }
+    }
+
+    fn draw_nests(&self, frame: &mut [u8]) {
+        let display = &self.settings.display;
         for (cell, pixel) in self.cells.iter().zip(frame.chunks_exact_mut(4)) {
-            pixel.copy_from_slice(&cell.color_rgba());
+            if let Some(color) = cell.nest_color_rgba(display.tribe_color(cell.tribe)) {
+                pixel.copy_from_slice(&color);
+            }
         }
     }
+
+    fn draw_food(&self, frame: &mut [u8]) {
+        let display = &self.settings.display;
+        for (i, (cell, pixel)) in self.cells.iter().zip(frame.chunks_exact_mut(4)).enumerate() {
+            let Some(color) = cell.food_color_rgba(display.food_gamma) else {
+                continue;
+            };
+
+            let (x, y) = self.index_to_coords(i);
+            if display.food_outline && self.is_food_edge(x, y) {
+                pixel.copy_from_slice(&FOOD_OUTLINE_COLOR);
+            } else {
+                pixel.copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// Whether a food cell borders a cell without food, or the edge of the world.
+    fn is_food_edge(&self, x: u16, y: u16) -> bool {
+        let neighbors = [
+            x.checked_sub(1).map(|x| (x, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+            Some((x + 1, y)).filter(|(x, _)| *x < self.settings.width),
+            Some((x, y + 1)).filter(|(_, y)| *y < self.settings.height),
+        ];
+        neighbors.iter().any(|neighbor| match neighbor {
+            Some((x, y)) => self.cells[self.coords_to_index(*x, *y)].food == 0,
+            None => true,
+        })
+    }
 }
 
 // Step
@@ -187,19 +422,27 @@ impl Simulation {
             return;
         }
 
+        // Every ant gets its own generator derived from a per-step seed, keeping the
+        // parallel sensing deterministic regardless of how rayon splits the work.
+        let step_seed = self.rng.u64(..);
         let ant_actions = self
             .ants
             .par_iter()
-            .map(|ant| ant.sense(self.sense_for_ant(ant), &self.settings.ant))
+            .enumerate()
+            .map(|(i, ant)| {
+                let mut rng = Rng::with_seed(step_seed.wrapping_add(i as u64));
+                ant.sense(self.sense_for_ant(ant), &self.settings.ant, &mut rng)
+            })
             .collect::<Vec<_>>();
 
-        for (ant, action) in self.ants.iter_mut().zip(ant_actions.into_iter()) {
+        for (ant, action) in self.ants.iter_mut().zip(ant_actions) {
             Self::apply_action(
                 ant,
                 action,
                 &self.settings,
                 &mut self.pheromones,
                 &mut self.cells,
+                &mut self.stats,
             );
         }
 
@@ -272,6 +515,24 @@ impl Simulation {
         }
     }
 
+    /// Where the ant's left, forward and right sensors sample the pheromones.
+    pub fn sensor_positions(&self, ant: &Ant) -> [(f32, f32); 3] {
+        let angle = self.settings.ant.sensor_angle;
+        let distance = self.settings.ant.sensor_distance;
+        [
+            Self::sensor_position(ant, ant.angle - angle, distance),
+            Self::sensor_position(ant, ant.angle, distance),
+            Self::sensor_position(ant, ant.angle + angle, distance),
+        ]
+    }
+
+    fn sensor_position(ant: &Ant, angle: f32, distance: f32) -> (f32, f32) {
+        (
+            ant.x + angle.cos() * distance,
+            ant.y + angle.sin() * distance,
+        )
+    }
+
     fn sample_pheromone(
         &self,
         ant: &Ant,
@@ -279,8 +540,7 @@ impl Simulation {
         dist: f32,
         pheromone_type: PheromoneType,
     ) -> f32 {
-        let sx = ant.x + angle.cos() * dist;
-        let sy = ant.y + angle.sin() * dist;
+        let (sx, sy) = Self::sensor_position(ant, angle, dist);
 
         if sx < 0.0
             || sy < 0.0
@@ -290,6 +550,10 @@ impl Simulation {
             return 0.0;
         }
 
+        if self.is_wall(sx as u16, sy as u16) {
+            return 0.0;
+        }
+
         self.pheromones
             .get(ant.tribe, pheromone_type, sx as u16, sy as u16)
     }
@@ -300,6 +564,7 @@ impl Simulation {
         settings: &SimulationSettings,
         pheromones: &mut Pheromones,
         cells: &mut [Cell],
+        stats: &mut SimulationStats,
     ) {
         let cell_idx = ant.y as usize * settings.width as usize + ant.x as usize;
         let cell = &mut cells[cell_idx];
@@ -322,6 +587,11 @@ impl Simulation {
             deposited_food,
         };
 
+        if feedback.deposited_food && ant.has_food {
+            stats.tribes[ant.tribe as usize].food_delivered += 1;
+        }
+
+        let (previous_x, previous_y) = (ant.x, ant.y);
         ant.update(&feedback, &settings.ant);
 
         // Bounce off walls
@@ -340,16 +610,94 @@ impl Simulation {
             ant.y = 2.0 * settings.height as f32 - ant.y - 1.0;
             ant.angle = -ant.angle;
         }
+
+        // Turn around in front of obstacles
+        let target_idx = ant.y as usize * settings.width as usize + ant.x as usize;
+        if cells[target_idx].flags.has_wall() {
+            ant.x = previous_x;
+            ant.y = previous_y;
+            ant.angle += std::f32::consts::PI;
+        }
     }
 
     fn collect_stats(&mut self, instant_start: Instant) {
+        self.stats.step += 1;
         self.stats.ant_count = self.ants.len() as u16;
         self.stats.ants_with_food = self.ants.par_iter().filter(|a| a.has_food).count() as u16;
         self.stats.total_food = self.cells.par_iter().map(|c| c.food as u64).sum();
 
+        for tribe in self.stats.tribes.iter_mut() {
+            tribe.ant_count = 0;
+            tribe.ants_with_food = 0;
+        }
+        for ant in &self.ants {
+            let tribe = &mut self.stats.tribes[ant.tribe as usize];
+            tribe.ant_count += 1;
+            tribe.ants_with_food += ant.has_food as u16;
+        }
+
         let duration = instant_start.elapsed().as_secs_f32();
         const SMOOTHING: f32 = 0.05;
         self.stats.avg_step_duration_secs =
             self.stats.avg_step_duration_secs * (1.0 - SMOOTHING) + duration * SMOOTHING;
     }
 }
+
+#[cfg(test)]
+pub(crate) mod fixture {
+    use super::*;
+
+    /// A small world with a nest and a food source, with ants at both.
+    pub(crate) fn world(seed: u64) -> Simulation {
+        let mut simulation = Simulation::new(SimulationSettings {
+            width: 64,
+            height: 48,
+            seed,
+            ..Default::default()
+        });
+        simulation.spawn_nest(10, 10, 0);
+        simulation.spawn_food(40, 30, 100);
+        for _ in 0..16 {
+            simulation.spawn_ant(10, 10, 0);
+        }
+        for _ in 0..4 {
+            simulation.spawn_ant(40, 30, 0);
+        }
+        simulation
+    }
+
+    /// The state that evolves with the steps. Stats are left out, they hold timings.
+    pub(crate) fn world_bytes(simulation: &Simulation) -> Vec<u8> {
+        let snapshot = simulation.snapshot();
+        postcard::to_stdvec(&(&snapshot.ants, &snapshot.cells, &snapshot.pheromones)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::fixture::world;
+
+    #[test]
+    fn walls_remove_the_ants_on_them() {
+        let mut simulation = world(1);
+        simulation.settings_mut().inspected_ant = Some(0);
+
+        simulation.spawn_wall(40, 30);
+        assert_eq!(simulation.ant_count(), 16);
+        assert_eq!(simulation.settings().inspected_ant, None);
+
+        simulation.edit_region(
+            &Region::Circle {
+                x: 10,
+                y: 10,
+                radius: 1,
+            },
+            RegionEdit::Wall,
+        );
+        assert_eq!(simulation.ant_count(), 0);
+
+        simulation.step();
+        assert_eq!(simulation.ant_count(), 0);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::simulation::pheromones::PheromoneType;
 use crate::simulation::settings::AntSettings;
+use crate::utils::color::interpolate_colors;
+use fastrand::Rng;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct AntSenses {
@@ -40,7 +43,7 @@ pub struct AntFeedback {
     pub deposited_food: bool,
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum AntMode {
     #[default]
@@ -51,7 +54,7 @@ pub enum AntMode {
     SearchingHome,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Ant {
     pub x: f32,
     pub y: f32,
@@ -66,22 +69,26 @@ pub struct Ant {
 }
 
 impl Ant {
-    pub fn color_rgba(&self, inspected: bool) -> [u8; 4] {
+    /// The ant's color, a lighter shade of its tribe's color that turns golden while it
+    /// carries food.
+    pub fn color_rgba(&self, tribe_color: [u8; 3], inspected: bool) -> [u8; 4] {
         if inspected {
             return [255, 255, 255, 255];
         }
 
-        if self.has_food {
-            [201, 160, 56, 255]
+        let [r, g, b] = tribe_color;
+        let target = if self.has_food {
+            [255, 200, 60, 255]
         } else {
-            [165, 102, 47, 255]
-        }
+            [255, 255, 255, 255]
+        };
+        interpolate_colors([r, g, b, 255], target, 0.4)
     }
 }
 
 // Sense and act
 impl Ant {
-    pub fn sense(&self, senses: AntSenses, settings: &AntSettings) -> AntAction {
+    pub fn sense(&self, senses: AntSenses, settings: &AntSettings, rng: &mut Rng) -> AntAction {
         let turn = if self.mode == AntMode::SearchingHome {
             self.spiral_turn(settings)
         } else if (self.mode == AntMode::Exploring && senses.food > 0)
@@ -90,7 +97,7 @@ impl Ant {
             self.angle + std::f32::consts::PI
         } else {
             senses.desired_turn(settings.turn_angle)
-        } + (fastrand::f32() - 0.5) * settings.wobble_strength;
+        } + (rng.f32() - 0.5) * settings.wobble_strength;
 
         let pheromone_strength = if self.mode == AntMode::FoodToHome {
             settings.pheromone_strength.max(senses.food as f32)
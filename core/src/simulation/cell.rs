@@ -1,26 +1,53 @@
+use crate::utils::color::interpolate_colors;
 use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
 pub struct Cell {
     pub flags: CellFlags,
     pub tribe: u8,
     pub food: u8,
 }
 
+const LITTLE_FOOD_COLOR: [u8; 4] = [25, 80, 25, 255];
+const MUCH_FOOD_COLOR: [u8; 4] = [150, 255, 110, 255];
+
 impl Cell {
-    pub fn color_rgba(&self) -> [u8; 4] {
-        if self.flags.has_home() {
-            [0, 0, 255, 255]
+    /// Walls and empty ground, below everything else.
+    pub fn ground_color_rgba(&self) -> [u8; 4] {
+        if self.flags.has_wall() {
+            [90, 90, 90, 255]
         } else {
-            [0, self.food, 0, 255]
+            [0, 0, 0, 255]
         }
     }
+
+    /// A darker shade of the tribe's color, if the cell is part of a nest.
+    pub fn nest_color_rgba(&self, tribe_color: [u8; 3]) -> Option<[u8; 4]> {
+        if !self.flags.has_home() || self.flags.has_wall() {
+            return None;
+        }
+
+        let [r, g, b] = tribe_color;
+        Some(interpolate_colors([r, g, b, 255], [0, 0, 0, 255], 0.35))
+    }
+
+    /// Brighter the more food there is, with a `gamma` below 1 lifting small amounts.
+    pub fn food_color_rgba(&self, gamma: f32) -> Option<[u8; 4]> {
+        if self.food == 0 || self.flags.has_wall() {
+            return None;
+        }
+
+        let t = (self.food as f32 / u8::MAX as f32).powf(gamma);
+        Some(interpolate_colors(LITTLE_FOOD_COLOR, MUCH_FOOD_COLOR, t))
+    }
 }
 
 bitflags! {
-    #[derive(Debug, Default, Copy, Clone)]
+    #[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
     pub struct CellFlags: u8 {
         const HAS_HOME = 0b0000_0001;
+        const HAS_WALL = 0b0000_0010;
     }
 }
 
@@ -32,4 +59,12 @@ impl CellFlags {
     pub fn set_home(&mut self, has_home: bool) {
         self.set(CellFlags::HAS_HOME, has_home);
     }
+
+    pub fn has_wall(&self) -> bool {
+        self.contains(CellFlags::HAS_WALL)
+    }
+
+    pub fn set_wall(&mut self, has_wall: bool) {
+        self.set(CellFlags::HAS_WALL, has_wall);
+    }
 }
@@ -0,0 +1,255 @@
+use crate::simulation::pheromones::PheromoneType;
+use crate::utils::color::interpolate_colors;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// How pheromone heat is turned into colors.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum Colormap {
+    /// The layer's tint, fading in with the heat. The main layer is tinted in its tribe's color.
+    #[default]
+    Tint,
+    Viridis,
+    Magma,
+}
+
+// Sampled at nine evenly spaced points from matplotlib's colormaps
+const VIRIDIS: [[u8; 3]; 9] = [
+    [68, 1, 84],
+    [71, 45, 123],
+    [59, 82, 139],
+    [44, 114, 142],
+    [33, 145, 140],
+    [40, 174, 128],
+    [94, 201, 98],
+    [173, 220, 48],
+    [253, 231, 37],
+];
+const MAGMA: [[u8; 3]; 9] = [
+    [0, 0, 4],
+    [28, 16, 68],
+    [79, 18, 123],
+    [129, 37, 129],
+    [181, 54, 122],
+    [229, 80, 100],
+    [251, 136, 97],
+    [254, 194, 135],
+    [252, 253, 191],
+];
+
+impl Colormap {
+    /// The color of a heat normalized to `0.0..=1.0`, transparent where there is no heat.
+    pub fn color(self, t: f32, tint: [u8; 3]) -> [u8; 4] {
+        let t = t.clamp(0.0, 1.0);
+        let alpha = (t * 255.0) as u8;
+        let [r, g, b] = match self {
+            Self::Tint => tint,
+            Self::Viridis => Self::sample(&VIRIDIS, t),
+            Self::Magma => Self::sample(&MAGMA, t),
+        };
+        [r, g, b, alpha]
+    }
+
+    fn sample(stops: &[[u8; 3]], t: f32) -> [u8; 3] {
+        let position = t * (stops.len() - 1) as f32;
+        let index = (position as usize).min(stops.len() - 2);
+        let [a, b] = [stops[index], stops[index + 1]].map(|[r, g, b]| [r, g, b, 255]);
+        let [r, g, b, _] = interpolate_colors(a, b, position - index as f32);
+        [r, g, b]
+    }
+}
+
+impl Display for Colormap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum HeatScale {
+    #[default]
+    Linear,
+    /// Makes faint trails visible next to strong ones.
+    Log,
+}
+
+impl HeatScale {
+    /// Maps `value` to `0.0..=1.0`, with `max_heat` and above at the top.
+    pub fn normalize(self, value: f32, max_heat: f32) -> f32 {
+        let t = match self {
+            Self::Linear => value / max_heat,
+            Self::Log => value.max(0.0).ln_1p() / max_heat.ln_1p(),
+        };
+        t.clamp(0.0, 1.0)
+    }
+}
+
+impl Display for HeatScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Things drawn on top of the ground, in an order of choice.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum RenderLayer {
+    Nests,
+    Food,
+    Pheromones,
+    Ants,
+}
+
+impl Display for RenderLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A pheromone layer drawn on top of the main one.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PheromoneLayer {
+    pub tribe: u8,
+    pub pheromone: PheromoneType,
+    pub tint: [u8; 3],
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// Default colors of the first eight tribes.
+const TRIBE_COLORS: [[u8; 3]; 8] = [
+    [230, 120, 40],
+    [60, 140, 255],
+    [200, 60, 200],
+    [240, 220, 60],
+    [60, 210, 200],
+    [230, 60, 70],
+    [150, 110, 255],
+    [140, 230, 90],
+];
+
+/// How the world is drawn, on top of the main pheromone layer chosen by
+/// [`SimulationSettings::drawn_pheromone`](crate::simulation::settings::SimulationSettings).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplaySettings {
+    /// Colors of ants, nests and pheromones per tribe, repeating for tribes past the end.
+    pub tribe_colors: Vec<[u8; 3]>,
+    pub colormap: Colormap,
+    pub heat_scale: HeatScale,
+    /// The tribe whose pheromones make up the main layer.
+    pub pheromone_tribe: u8,
+    /// The pheromone amount drawn at full strength.
+    pub max_heat: f32,
+    /// Scales every layer to its own hottest cell instead of `max_heat`.
+    pub auto_max_heat: bool,
+    /// Always drawn in their tint, so they stay apart from the main layer and each other.
+    pub overlay_layers: Vec<PheromoneLayer>,
+    pub show_ants: bool,
+    pub show_food: bool,
+    pub show_nests: bool,
+    /// Below 1 makes small amounts of food stand out more.
+    pub food_gamma: f32,
+    /// Highlights the edges of food patches.
+    pub food_outline: bool,
+    /// Drawn bottom to top, layers missing here are drawn on top of the others. Hiding layers
+    /// is up to the `show_*` toggles, the drawn pheromone and the overlay layers.
+    pub layer_order: Vec<RenderLayer>,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            tribe_colors: TRIBE_COLORS.to_vec(),
+            colormap: Colormap::default(),
+            heat_scale: HeatScale::default(),
+            pheromone_tribe: 0,
+            max_heat: 10.0,
+            auto_max_heat: false,
+            overlay_layers: Vec::new(),
+            show_ants: true,
+            show_food: true,
+            show_nests: true,
+            food_gamma: 0.5,
+            food_outline: false,
+            layer_order: vec![
+                RenderLayer::Nests,
+                RenderLayer::Food,
+                RenderLayer::Pheromones,
+                RenderLayer::Ants,
+            ],
+        }
+    }
+}
+
+impl DisplaySettings {
+    pub fn tribe_color(&self, tribe: u8) -> [u8; 3] {
+        if self.tribe_colors.is_empty() {
+            return [255, 255, 255];
+        }
+        self.tribe_colors[tribe as usize % self.tribe_colors.len()]
+    }
+
+    /// Every layer exactly once, bottom to top.
+    pub fn render_layers(&self) -> Vec<RenderLayer> {
+        let mut layers = Vec::new();
+        for layer in self.layer_order.iter().copied().chain(RenderLayer::iter()) {
+            if !layers.contains(&layer) {
+                layers.push(layer);
+            }
+        }
+        layers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_scale_is_proportional_and_clamped() {
+        assert_eq!(HeatScale::Linear.normalize(0.0, 10.0), 0.0);
+        assert_eq!(HeatScale::Linear.normalize(2.5, 10.0), 0.25);
+        assert_eq!(HeatScale::Linear.normalize(10.0, 10.0), 1.0);
+        assert_eq!(HeatScale::Linear.normalize(25.0, 10.0), 1.0);
+        assert_eq!(HeatScale::Linear.normalize(-1.0, 10.0), 0.0);
+    }
+
+    #[test]
+    fn log_scale_lifts_faint_values_and_is_clamped() {
+        let faint = HeatScale::Log.normalize(1.0, 100.0);
+        assert!(faint > HeatScale::Linear.normalize(1.0, 100.0));
+        assert_eq!(HeatScale::Log.normalize(0.0, 100.0), 0.0);
+        assert!((HeatScale::Log.normalize(100.0, 100.0) - 1.0).abs() < 1e-6);
+        assert_eq!(HeatScale::Log.normalize(1000.0, 100.0), 1.0);
+        assert_eq!(HeatScale::Log.normalize(-5.0, 100.0), 0.0);
+    }
+
+    #[test]
+    fn normalize_is_monotonic() {
+        for scale in [HeatScale::Linear, HeatScale::Log] {
+            let values = (0..=20)
+                .map(|i| scale.normalize(i as f32, 20.0))
+                .collect::<Vec<_>>();
+            assert!(values.windows(2).all(|pair| pair[0] <= pair[1]), "{scale}");
+        }
+    }
+
+    #[test]
+    fn render_layers_holds_every_layer_once() {
+        let display = DisplaySettings {
+            layer_order: vec![RenderLayer::Ants, RenderLayer::Food, RenderLayer::Ants],
+            ..Default::default()
+        };
+        assert_eq!(
+            display.render_layers(),
+            [
+                RenderLayer::Ants,
+                RenderLayer::Food,
+                RenderLayer::Nests,
+                RenderLayer::Pheromones
+            ]
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use crate::simulation::Simulation;
+
+/// A connected area of cells with food.
+#[derive(Debug, Clone, Copy)]
+pub struct FoodPatch {
+    /// The center of the patch's cells.
+    pub x: f32,
+    pub y: f32,
+    pub cells: u32,
+    pub amount: u32,
+}
+
+impl Simulation {
+    /// Finds the patches of food in the world, with cells counting as connected by their edges.
+    pub fn food_patches(&self) -> Vec<FoodPatch> {
+        let (width, height) = (self.settings.width, self.settings.height);
+        let mut visited = vec![false; self.cells.len()];
+        let mut patches = Vec::new();
+        let mut stack = Vec::new();
+
+        for start in 0..self.cells.len() {
+            if visited[start] || self.cells[start].food == 0 {
+                continue;
+            }
+
+            let mut patch = FoodPatch {
+                x: 0.0,
+                y: 0.0,
+                cells: 0,
+                amount: 0,
+            };
+            visited[start] = true;
+            stack.push(start);
+
+            while let Some(index) = stack.pop() {
+                let (x, y) = self.index_to_coords(index);
+                patch.x += x as f32;
+                patch.y += y as f32;
+                patch.cells += 1;
+                patch.amount += self.cells[index].food as u32;
+
+                let neighbors = [
+                    (x > 0).then(|| index - 1),
+                    (x + 1 < width).then(|| index + 1),
+                    (y > 0).then(|| index - width as usize),
+                    (y + 1 < height).then(|| index + width as usize),
+                ];
+                for neighbor in neighbors.into_iter().flatten() {
+                    if !visited[neighbor] && self.cells[neighbor].food > 0 {
+                        visited[neighbor] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+
+            // Cell centers are half a cell off their coordinates
+            patch.x = patch.x / patch.cells as f32 + 0.5;
+            patch.y = patch.y / patch.cells as f32 + 0.5;
+            patches.push(patch);
+        }
+
+        patches
+    }
+}
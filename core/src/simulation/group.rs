@@ -0,0 +1,117 @@
+use crate::simulation::Simulation;
+use crate::simulation::ant::AntMode;
+use crate::simulation::region::Region;
+use serde::{Deserialize, Serialize};
+
+/// Something done to a group of ants at once.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AntGroupEdit {
+    Delete,
+    Tribe { tribe: u8 },
+    Mode { mode: AntMode },
+}
+
+/// Aggregate information about a group of ants.
+#[derive(Debug, Clone, Default)]
+pub struct AntGroupStats {
+    pub count: u32,
+    pub carrying_food: u32,
+    /// Ants per mode, indexed by the mode's discriminant.
+    pub modes: [u32; 3],
+    /// Ants per tribe, indexed by the tribe.
+    pub tribes: Vec<u32>,
+    /// The circular mean of the ants' headings, in radians.
+    pub mean_heading: Option<f32>,
+    /// How closely the headings agree, from 0 (all over the place) to 1 (all the same).
+    pub heading_alignment: f32,
+}
+
+impl AntGroupStats {
+    pub fn mode_count(&self, mode: AntMode) -> u32 {
+        self.modes[mode as usize]
+    }
+}
+
+impl Simulation {
+    /// The selected ant indices, in ascending order.
+    pub fn selected_ants(&self) -> &[usize] {
+        &self.selected_ants
+    }
+
+    pub fn select_ants(&mut self, region: &Region) {
+        self.selected_ants = self
+            .ants
+            .iter()
+            .enumerate()
+            .filter(|(_, ant)| region.contains(ant.x as i32, ant.y as i32))
+            .map(|(i, _)| i)
+            .collect();
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected_ants.clear();
+    }
+
+    pub fn group_stats(&self, ants: &[usize]) -> AntGroupStats {
+        let mut stats = AntGroupStats {
+            tribes: vec![0; self.settings.tribe_count as usize],
+            ..Default::default()
+        };
+
+        let (mut heading_x, mut heading_y) = (0.0, 0.0);
+        for ant in ants.iter().filter_map(|i| self.ants.get(*i)) {
+            stats.count += 1;
+            stats.carrying_food += ant.has_food as u32;
+            stats.modes[ant.mode as usize] += 1;
+            if let Some(count) = stats.tribes.get_mut(ant.tribe as usize) {
+                *count += 1;
+            }
+            heading_x += ant.angle.cos();
+            heading_y += ant.angle.sin();
+        }
+
+        if stats.count > 0 {
+            let length = f32::hypot(heading_x, heading_y);
+            stats.heading_alignment = length / stats.count as f32;
+            if length > f32::EPSILON {
+                stats.mean_heading = Some(heading_y.atan2(heading_x));
+            }
+        }
+
+        stats
+    }
+
+    /// Edits the ants at the given indices, which have to be in ascending order.
+    pub fn edit_ants(&mut self, ants: &[usize], edit: AntGroupEdit) {
+        match edit {
+            AntGroupEdit::Delete => {
+                let mut index = 0;
+                self.ants.retain(|_| {
+                    let keep = ants.binary_search(&index).is_err();
+                    index += 1;
+                    keep
+                });
+                if self.ants.len() != index {
+                    self.on_ant_indices_shifted();
+                }
+            }
+            AntGroupEdit::Tribe { tribe } => {
+                if tribe >= self.settings.tribe_count {
+                    return;
+                }
+                for i in ants {
+                    if let Some(ant) = self.ants.get_mut(*i) {
+                        ant.tribe = tribe;
+                    }
+                }
+            }
+            AntGroupEdit::Mode { mode } => {
+                for i in ants {
+                    if let Some(ant) = self.ants.get_mut(*i) {
+                        ant.mode = mode;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,24 @@
 use crate::simulation::ant::Ant;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use strum_macros::EnumIter;
 
 const PHEROMONE_COUNT: usize = 2;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, IntoPrimitive, TryFromPrimitive, EnumIter)]
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
 #[repr(u8)]
 pub enum PheromoneType {
     Home = 0,
@@ -101,6 +113,19 @@ impl Pheromones {
         });
     }
 
+    pub fn layers(&self) -> &[Vec<f32>] {
+        &self.layers
+    }
+
+    /// Rebuilds the grid from layers as returned by [`Pheromones::layers`].
+    pub fn from_layers(width: u16, height: u16, layers: Vec<Vec<f32>>) -> Self {
+        Self {
+            layers,
+            width,
+            height,
+        }
+    }
+
     pub fn tribe_count(&self) -> u8 {
         (self.layers.len() / PHEROMONE_COUNT) as u8
     }
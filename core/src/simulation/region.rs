@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+/// An area of the cell grid, used to place things in bulk.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Region {
+    Point {
+        x: u16,
+        y: u16,
+    },
+    Circle {
+        x: u16,
+        y: u16,
+        radius: u16,
+    },
+    Rectangle {
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+    },
+    /// All cells within `radius` of the segment between the two points.
+    Line {
+        from_x: u16,
+        from_y: u16,
+        to_x: u16,
+        to_y: u16,
+        radius: u16,
+    },
+}
+
+/// Something done to every cell of a [`Region`] at once.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RegionEdit {
+    /// Spawns on average `density` ants per free cell.
+    Ants {
+        tribe: u8,
+        density: f32,
+    },
+    Nest {
+        tribe: u8,
+    },
+    Food {
+        amount: u8,
+    },
+    Wall,
+    /// Removes ants, nests, food and walls.
+    Erase,
+}
+
+impl Region {
+    /// All cells covered by the region, clipped to a grid of the given size.
+    pub fn cells(&self, grid_width: u16, grid_height: u16) -> Vec<(u16, u16)> {
+        let (min_x, min_y, max_x, max_y) = self.bounds();
+        let max_x = max_x.min(grid_width as i32 - 1);
+        let max_y = max_y.min(grid_height as i32 - 1);
+
+        let mut cells = Vec::new();
+        for y in min_y.max(0)..=max_y {
+            for x in min_x.max(0)..=max_x {
+                if self.contains(x, y) {
+                    cells.push((x as u16, y as u16));
+                }
+            }
+        }
+        cells
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        match *self {
+            Self::Point { x: px, y: py } => x == px as i32 && y == py as i32,
+            Self::Circle {
+                x: cx,
+                y: cy,
+                radius,
+            } => {
+                let (dx, dy) = (x - cx as i32, y - cy as i32);
+                dx * dx + dy * dy <= radius as i32 * radius as i32
+            }
+            Self::Rectangle {
+                x: rx,
+                y: ry,
+                width,
+                height,
+            } => {
+                x >= rx as i32
+                    && y >= ry as i32
+                    && x < rx as i32 + width as i32
+                    && y < ry as i32 + height as i32
+            }
+            Self::Line {
+                from_x,
+                from_y,
+                to_x,
+                to_y,
+                radius,
+            } => {
+                let (ax, ay) = (from_x as f32, from_y as f32);
+                let (dx, dy) = (to_x as f32 - ax, to_y as f32 - ay);
+                let length_squared = dx * dx + dy * dy;
+                let t = if length_squared > 0.0 {
+                    (((x as f32 - ax) * dx + (y as f32 - ay) * dy) / length_squared).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let (px, py) = (ax + t * dx - x as f32, ay + t * dy - y as f32);
+                px * px + py * py <= radius as f32 * radius as f32
+            }
+        }
+    }
+
+    /// Inclusive bounding box as `(min_x, min_y, max_x, max_y)`, not clipped to any grid.
+    pub fn bounds(&self) -> (i32, i32, i32, i32) {
+        match *self {
+            Self::Point { x, y } => (x as i32, y as i32, x as i32, y as i32),
+            Self::Circle { x, y, radius } => {
+                let (x, y, r) = (x as i32, y as i32, radius as i32);
+                (x - r, y - r, x + r, y + r)
+            }
+            Self::Rectangle {
+                x,
+                y,
+                width,
+                height,
+            } => (
+                x as i32,
+                y as i32,
+                x as i32 + width as i32 - 1,
+                y as i32 + height as i32 - 1,
+            ),
+            Self::Line {
+                from_x,
+                from_y,
+                to_x,
+                to_y,
+                radius,
+            } => {
+                let r = radius as i32;
+                (
+                    from_x.min(to_x) as i32 - r,
+                    from_y.min(to_y) as i32 - r,
+                    from_x.max(to_x) as i32 + r,
+                    from_y.max(to_y) as i32 + r,
+                )
+            }
+        }
+    }
+}
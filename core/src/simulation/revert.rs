@@ -0,0 +1,216 @@
+use crate::simulation::Simulation;
+use crate::simulation::ant::Ant;
+use crate::simulation::cell::Cell;
+use crate::simulation::pheromones::Pheromones;
+use crate::simulation::region::Region;
+use serde::{Deserialize, Serialize};
+
+/// What a world edit changed, captured before it was applied so it can be undone.
+///
+/// Reverts only restore the world correctly in the reverse order of their edits, since ants
+/// spawned by an edit are expected at the end of the ant list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Revert {
+    ant_count: usize,
+    removed_ants: Vec<(usize, Ant)>,
+    changed_ants: Vec<(usize, Ant)>,
+    cells: Vec<(usize, Cell)>,
+    pheromones: Option<Vec<Vec<f32>>>,
+}
+
+impl Revert {
+    /// Captures the cells of `region`, plus the ants in it if the edit is going to remove them.
+    pub fn capture(simulation: &Simulation, region: &Region, removes_ants: bool) -> Self {
+        let settings = simulation.settings();
+        let cells = region
+            .cells(settings.width, settings.height)
+            .into_iter()
+            .map(|(x, y)| {
+                let index = simulation.coords_to_index(x, y);
+                (index, simulation.cells[index])
+            })
+            .collect();
+
+        let removed_ants = if removes_ants {
+            simulation
+                .ants
+                .iter()
+                .enumerate()
+                .filter(|(_, ant)| region.contains(ant.x as i32, ant.y as i32))
+                .map(|(i, ant)| (i, ant.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            ant_count: simulation.ants.len(),
+            removed_ants,
+            changed_ants: Vec::new(),
+            cells,
+            pheromones: None,
+        }
+    }
+
+    /// Captures the given ants, in ascending order, before they are edited or removed.
+    pub fn capture_ants(simulation: &Simulation, ants: &[usize], removes_ants: bool) -> Self {
+        let captured = ants
+            .iter()
+            .filter_map(|i| simulation.ants.get(*i).map(|ant| (*i, ant.clone())))
+            .collect();
+
+        let (removed_ants, changed_ants) = if removes_ants {
+            (captured, Vec::new())
+        } else {
+            (Vec::new(), captured)
+        };
+
+        Self {
+            ant_count: simulation.ants.len(),
+            removed_ants,
+            changed_ants,
+            cells: Vec::new(),
+            pheromones: None,
+        }
+    }
+
+    /// Captures the whole world, for edits like clearing it.
+    pub fn capture_all(simulation: &Simulation) -> Self {
+        Self {
+            ant_count: simulation.ants.len(),
+            removed_ants: simulation.ants.iter().cloned().enumerate().collect(),
+            changed_ants: Vec::new(),
+            cells: simulation.cells.iter().copied().enumerate().collect(),
+            pheromones: Some(simulation.pheromones.layers().to_vec()),
+        }
+    }
+}
+
+impl Simulation {
+    pub fn revert(&mut self, revert: &Revert) {
+        if self.ants.len() > revert.ant_count {
+            self.ants.truncate(revert.ant_count);
+            self.on_ant_indices_shifted();
+        }
+        if !revert.removed_ants.is_empty() {
+            for (index, ant) in &revert.removed_ants {
+                let index = (*index).min(self.ants.len());
+                self.ants.insert(index, ant.clone());
+            }
+            self.on_ant_indices_shifted();
+        }
+        for (index, ant) in &revert.changed_ants {
+            if let Some(target) = self.ants.get_mut(*index) {
+                *target = ant.clone();
+            }
+        }
+
+        for (index, cell) in &revert.cells {
+            if let Some(target) = self.cells.get_mut(*index) {
+                *target = *cell;
+            }
+        }
+
+        if let Some(layers) = &revert.pheromones {
+            self.pheromones =
+                Pheromones::from_layers(self.settings.width, self.settings.height, layers.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::fixture::{world, world_bytes};
+    use crate::simulation::group::AntGroupEdit;
+    use crate::simulation::region::RegionEdit;
+
+    #[test]
+    fn erase_is_undone() {
+        let mut simulation = world(11);
+        let before = world_bytes(&simulation);
+        let region = Region::Circle {
+            x: 40,
+            y: 30,
+            radius: 3,
+        };
+
+        let revert = Revert::capture(&simulation, &region, true);
+        simulation.edit_region(&region, RegionEdit::Erase);
+        assert_eq!(simulation.ant_count(), 16);
+
+        simulation.revert(&revert);
+        assert_eq!(world_bytes(&simulation), before);
+    }
+
+    #[test]
+    fn walls_are_undone() {
+        let mut simulation = world(11);
+        let before = world_bytes(&simulation);
+        let region = Region::Point { x: 40, y: 30 };
+
+        let revert = Revert::capture(&simulation, &region, true);
+        simulation.edit_region(&region, RegionEdit::Wall);
+        assert_eq!(simulation.ant_count(), 16);
+
+        simulation.revert(&revert);
+        assert_eq!(world_bytes(&simulation), before);
+    }
+
+    #[test]
+    fn spawning_is_undone() {
+        let mut simulation = world(11);
+        let before = world_bytes(&simulation);
+        let region = Region::Rectangle {
+            x: 20,
+            y: 20,
+            width: 4,
+            height: 4,
+        };
+
+        let revert = Revert::capture(&simulation, &region, false);
+        simulation.edit_region(&region, RegionEdit::Nest { tribe: 0 });
+        simulation.spawn_ant(22, 22, 0);
+
+        simulation.revert(&revert);
+        assert_eq!(world_bytes(&simulation), before);
+    }
+
+    #[test]
+    fn ant_edits_are_undone() {
+        let mut simulation = world(11);
+        let before = world_bytes(&simulation);
+
+        let revert = Revert::capture_ants(&simulation, &[1, 3], false);
+        simulation.edit_ants(&[1, 3], AntGroupEdit::Tribe { tribe: 1 });
+        simulation.revert(&revert);
+        assert_eq!(world_bytes(&simulation), before);
+
+        let revert = Revert::capture_ants(&simulation, &[0, 2], true);
+        simulation.edit_ants(&[0, 2], AntGroupEdit::Delete);
+        assert_eq!(simulation.ant_count(), 18);
+        simulation.revert(&revert);
+        assert_eq!(world_bytes(&simulation), before);
+    }
+
+    #[test]
+    fn reinserting_ants_forgets_index_references() {
+        let mut simulation = world(11);
+        let region = Region::Point { x: 40, y: 30 };
+        let revert = Revert::capture(&simulation, &region, true);
+        simulation.edit_region(&region, RegionEdit::Erase);
+
+        simulation.settings_mut().inspected_ant = Some(1);
+        simulation.select_ants(&Region::Rectangle {
+            x: 0,
+            y: 0,
+            width: 64,
+            height: 48,
+        });
+        assert!(!simulation.selected_ants().is_empty());
+
+        simulation.revert(&revert);
+        assert_eq!(simulation.settings().inspected_ant, None);
+        assert!(simulation.selected_ants().is_empty());
+    }
+}
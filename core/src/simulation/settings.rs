@@ -1,5 +1,9 @@
+use crate::simulation::display::DisplaySettings;
 use crate::simulation::pheromones::PheromoneType;
+use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct AntSettings {
     pub pheromone_strength: f32,
     pub sensor_angle: f32,
@@ -24,20 +28,30 @@ impl Default for AntSettings {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SimulationSettings {
     pub width: u16,
     pub height: u16,
     pub ant: AntSettings,
     pub tribe_count: u8,
+    /// Ticks per second, each running `speed_multiplier` steps.
     pub steps_per_second: u8,
+    /// Steps run per tick, multiplying `steps_per_second` past its limit of 255.
+    pub speed_multiplier: u16,
+    /// Runs steps as fast as possible and only draws frames at the display rate.
+    /// `steps_per_second` and `speed_multiplier` are ignored while it is on.
+    pub fast_forward: bool,
     pub pheromone_decay: f32,
     pub pheromone_diffusion: f32,
     pub nest_pheromone_strength: f32,
     pub paused: bool,
     pub drawn_pheromone: Option<PheromoneType>,
-    pub drawn_pheromone_max_heat: f32,
-    pub drawn_pheromone_tribe: u8,
+    pub display: DisplaySettings,
+    #[serde(skip)]
     pub inspected_ant: Option<u16>,
+    /// Seeds all randomness of the simulation, equal seeds and inputs give equal runs.
+    pub seed: u64,
 }
 
 impl Default for SimulationSettings {
@@ -48,14 +62,16 @@ impl Default for SimulationSettings {
             ant: AntSettings::default(),
             tribe_count: 4,
             steps_per_second: 60,
+            speed_multiplier: 1,
+            fast_forward: false,
             pheromone_decay: 0.9975,
             pheromone_diffusion: 0.25,
             nest_pheromone_strength: 5.0,
             drawn_pheromone: Some(PheromoneType::Home),
-            drawn_pheromone_max_heat: 10.0,
-            drawn_pheromone_tribe: 0,
+            display: DisplaySettings::default(),
             paused: false,
             inspected_ant: None,
+            seed: fastrand::u64(..),
         }
     }
 }
@@ -65,3 +81,89 @@ impl SimulationSettings {
         self.width as usize * self.height as usize
     }
 }
+
+/// A partial update of [`AntSettings`], `None` fields are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AntSettingsPatch {
+    pub pheromone_strength: Option<f32>,
+    pub sensor_angle: Option<f32>,
+    pub sensor_distance: Option<f32>,
+    pub speed: Option<f32>,
+    pub turn_angle: Option<f32>,
+    pub wobble_strength: Option<f32>,
+    pub spiral_expansion_rate: Option<f32>,
+}
+
+impl AntSettingsPatch {
+    /// A patch setting every field to the values of `settings`.
+    pub fn from_settings(settings: &AntSettings) -> Self {
+        Self {
+            pheromone_strength: Some(settings.pheromone_strength),
+            sensor_angle: Some(settings.sensor_angle),
+            sensor_distance: Some(settings.sensor_distance),
+            speed: Some(settings.speed),
+            turn_angle: Some(settings.turn_angle),
+            wobble_strength: Some(settings.wobble_strength),
+            spiral_expansion_rate: Some(settings.spiral_expansion_rate),
+        }
+    }
+
+    pub fn apply(&self, settings: &mut AntSettings) {
+        apply_field(&mut settings.pheromone_strength, self.pheromone_strength);
+        apply_field(&mut settings.sensor_angle, self.sensor_angle);
+        apply_field(&mut settings.sensor_distance, self.sensor_distance);
+        apply_field(&mut settings.speed, self.speed);
+        apply_field(&mut settings.turn_angle, self.turn_angle);
+        apply_field(&mut settings.wobble_strength, self.wobble_strength);
+        apply_field(
+            &mut settings.spiral_expansion_rate,
+            self.spiral_expansion_rate,
+        );
+    }
+}
+
+/// A partial update of the settings that affect a running simulation's behavior.
+///
+/// World dimensions and the tribe count are fixed once a simulation is created, and the
+/// display-only settings are synced through the threaded shared state instead.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SettingsPatch {
+    pub ant: AntSettingsPatch,
+    pub pheromone_decay: Option<f32>,
+    pub pheromone_diffusion: Option<f32>,
+    pub nest_pheromone_strength: Option<f32>,
+}
+
+impl SettingsPatch {
+    /// A patch setting every patchable field to the values of `settings`.
+    pub fn from_settings(settings: &SimulationSettings) -> Self {
+        Self {
+            ant: AntSettingsPatch::from_settings(&settings.ant),
+            pheromone_decay: Some(settings.pheromone_decay),
+            pheromone_diffusion: Some(settings.pheromone_diffusion),
+            nest_pheromone_strength: Some(settings.nest_pheromone_strength),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn apply(&self, settings: &mut SimulationSettings) {
+        self.ant.apply(&mut settings.ant);
+        apply_field(&mut settings.pheromone_decay, self.pheromone_decay);
+        apply_field(&mut settings.pheromone_diffusion, self.pheromone_diffusion);
+        apply_field(
+            &mut settings.nest_pheromone_strength,
+            self.nest_pheromone_strength,
+        );
+    }
+}
+
+fn apply_field<T>(field: &mut T, value: Option<T>) {
+    if let Some(value) = value {
+        *field = value;
+    }
+}
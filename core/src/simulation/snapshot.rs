@@ -0,0 +1,33 @@
+use crate::simulation::ant::Ant;
+use crate::simulation::cell::Cell;
+use crate::simulation::settings::SimulationSettings;
+use crate::simulation::stats::SimulationStats;
+use crate::utils::compression::{compress, decompress};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// The complete state of a [`Simulation`](crate::simulation::Simulation), restoring it
+/// continues the run exactly as if it had never been interrupted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationSnapshot {
+    pub settings: SimulationSettings,
+    pub ants: Vec<Ant>,
+    pub cells: Vec<Cell>,
+    pub pheromones: Vec<Vec<f32>>,
+    pub stats: SimulationStats,
+    pub rng_state: u64,
+}
+
+impl SimulationSnapshot {
+    pub fn step(&self) -> u64 {
+        self.stats.step
+    }
+
+    pub fn to_compressed_bytes(&self) -> io::Result<Vec<u8>> {
+        compress(self)
+    }
+
+    pub fn from_compressed_bytes(compressed: &[u8]) -> io::Result<Self> {
+        decompress(compressed)
+    }
+}
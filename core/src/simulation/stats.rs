@@ -1,7 +1,19 @@
-#[derive(Debug, Default)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SimulationStats {
+    pub step: u64,
     pub ant_count: u16,
     pub ants_with_food: u16,
     pub total_food: u64,
     pub avg_step_duration_secs: f32,
+    pub tribes: Vec<TribeStats>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TribeStats {
+    pub ant_count: u16,
+    pub ants_with_food: u16,
+    /// Total food units delivered to this tribe's nests since the simulation started.
+    pub food_delivered: u64,
 }
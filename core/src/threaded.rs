@@ -1,51 +1,97 @@
-use crate::simulation::settings::SimulationSettings;
+use crate::checkpoint::{CheckpointRing, CheckpointSettings};
+use crate::export::animation::{AnimationFormat, AnimationSettings};
+use crate::export::frame::save_png;
+use crate::replay::ReplayLog;
+use crate::simulation::display::DisplaySettings;
+use crate::simulation::food::FoodPatch;
+use crate::simulation::group::AntGroupEdit;
+use crate::simulation::region::{Region, RegionEdit};
+use crate::simulation::settings::{SettingsPatch, SimulationSettings};
 use crate::simulation::Simulation;
+use crate::simulation::stats::SimulationStats;
 use crate::threaded::ant_buffer::AntBuffer;
+use crate::threaded::ant_history::AntHistory;
 use crate::threaded::command::SimulationCommand;
 use crate::threaded::context::ThreadedContext;
 use crate::threaded::event::SimulationEvent;
+use crate::threaded::history::EditHistory;
 use crate::threaded::shared::SharedState;
+use crate::threaded::stats_history::StatsHistory;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{Receiver, Sender};
 use std::sync::Arc;
 use std::thread;
 use std::thread::JoinHandle;
 use triple_buffer::TripleBuffer;
 
-mod ant_buffer;
+pub mod ant_buffer;
+pub mod ant_history;
 mod command;
 mod context;
 pub mod event;
+mod history;
 mod shared;
+pub mod stats_history;
 
 pub struct ThreadedSimulation {
     command_tx: Sender<SimulationCommand>,
     event_rx: Receiver<SimulationEvent>,
+    stats_rx: Receiver<SimulationStats>,
+    stats_history: StatsHistory,
     frame_reader: triple_buffer::Output<Vec<u8>>,
     ant_reader: triple_buffer::Output<Option<AntBuffer>>,
+    settings_reader: triple_buffer::Output<SimulationSettings>,
+    food_patches_reader: triple_buffer::Output<Vec<FoodPatch>>,
     state: Arc<SharedState>,
+    width: u16,
+    height: u16,
     _thread: JoinHandle<()>,
 }
 
 impl ThreadedSimulation {
     pub fn spawn(settings: SimulationSettings) -> Self {
+        Self::spawn_with(Simulation::new(settings))
+    }
+
+    /// Moves an already populated simulation onto its own thread.
+    pub fn spawn_with(simulation: Simulation) -> Self {
         let (command_tx, command_rx) = std::sync::mpsc::channel();
         let (event_tx, event_rx) = std::sync::mpsc::channel();
+        let (stats_tx, stats_rx) = std::sync::mpsc::channel();
 
-        let shared = Arc::new(SharedState::from_settings(&settings));
+        let shared = Arc::new(SharedState::from_settings(simulation.settings()));
         let shared_clone = shared.clone();
 
-        let buf_size = settings.cell_count() * 4;
+        let width = simulation.settings().width;
+        let height = simulation.settings().height;
+        let buf_size = simulation.settings().cell_count() * 4;
         let (frame_writer, frame_reader) = TripleBuffer::new(&vec![0u8; buf_size]).split();
         let (ant_writer, ant_reader) = TripleBuffer::new(&None).split();
+        let (settings_writer, settings_reader) = TripleBuffer::new(simulation.settings()).split();
+        let (food_patches_writer, food_patches_reader) = TripleBuffer::new(&Vec::new()).split();
 
         let thread = thread::spawn(move || {
+            let replay_log = ReplayLog::new(simulation.snapshot());
             let context = ThreadedContext {
-                simulation: Simulation::new(settings),
+                simulation,
                 command_rx,
                 event_tx,
+                stats_tx,
                 shared: shared_clone,
                 frame_writer,
                 ant_writer,
+                settings_writer,
+                food_patches_writer,
+                stats_exporter: None,
+                frame_recorder: None,
+                animation_recorder: None,
+                replay_log,
+                replay_player: None,
+                checkpoints: CheckpointRing::new(CheckpointSettings::default()),
+                pending_steps: 0,
+                history: EditHistory::default(),
+                ant_history: AntHistory::default(),
             };
             context.run();
         });
@@ -53,9 +99,15 @@ impl ThreadedSimulation {
         Self {
             command_tx,
             event_rx,
+            stats_rx,
+            stats_history: StatsHistory::default(),
             frame_reader,
             ant_reader,
+            settings_reader,
+            food_patches_reader,
             state: shared,
+            width,
+            height,
             _thread: thread,
         }
     }
@@ -68,15 +120,77 @@ impl ThreadedSimulation {
         self.event_rx.try_recv().ok()
     }
 
+    /// Moves the stats of the steps run since the last call into the history.
+    pub fn update_stats_history(&mut self) {
+        for stats in self.stats_rx.try_iter() {
+            self.stats_history.push(stats);
+        }
+    }
+
+    /// The stats of the most recent steps, as of the last [`Self::update_stats_history`].
+    pub fn stats_history(&self) -> &StatsHistory {
+        &self.stats_history
+    }
+
     pub fn draw(&mut self, frame: &mut [u8]) {
         let buffer = self.frame_reader.read();
         frame.copy_from_slice(buffer);
     }
 
+    /// The most recently published frame.
+    pub fn frame(&mut self) -> &[u8] {
+        self.frame_reader.read()
+    }
+
+    /// Saves the most recently published frame as PNG, upscaled by `scale`.
+    pub fn screenshot(&mut self, path: impl AsRef<Path>, scale: u32) -> io::Result<()> {
+        let buffer = self.frame_reader.read();
+        save_png(path, buffer, self.width as u32, self.height as u32, scale)
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
     pub fn inspected_ant(&mut self) -> &Option<AntBuffer> {
         self.ant_reader.read()
     }
 
+    /// The food patches published with the most recent frame, while
+    /// [`SharedState::wants_food_patches`] is set.
+    pub fn food_patches(&mut self) -> &[FoodPatch] {
+        self.food_patches_reader.read()
+    }
+
+    /// The simulation's settings as of its most recent tick.
+    pub fn settings(&mut self) -> &SimulationSettings {
+        self.settings_reader.read()
+    }
+
+    /// Changes the given settings of the running simulation, recording the change for replays.
+    pub fn update_settings(&self, patch: SettingsPatch) {
+        self.send_command(SimulationCommand::UpdateSettings { patch });
+    }
+
+    /// Applies every setting that can change at runtime, e.g. from a preset.
+    ///
+    /// The world size and tribe count stay as they are.
+    pub fn apply_settings(&self, settings: &SimulationSettings) {
+        self.update_settings(SettingsPatch::from_settings(settings));
+        self.state.set_steps_per_second(settings.steps_per_second);
+        self.state.set_speed_multiplier(settings.speed_multiplier);
+        self.state.set_drawn_pheromone(settings.drawn_pheromone);
+        self.set_display_settings(settings.display.clone());
+    }
+
+    pub fn set_display_settings(&self, settings: DisplaySettings) {
+        self.send_command(SimulationCommand::SetDisplaySettings { settings });
+    }
+
     pub fn toggle_paused(&self) {
         self.state.set_paused(!self.state.is_paused());
     }
@@ -85,6 +199,11 @@ impl ThreadedSimulation {
         &self.state
     }
 
+    /// Pauses the simulation and advances it by exactly `count` steps.
+    pub fn step(&self, count: u64) {
+        self.send_command(SimulationCommand::Step { count });
+    }
+
     pub fn clear(&self) {
         self.send_command(SimulationCommand::Clear);
     }
@@ -101,9 +220,111 @@ impl ThreadedSimulation {
         self.send_command(SimulationCommand::SpawnFood { x, y, amount });
     }
 
+    /// Applies `edit` to the whole region at once.
+    pub fn edit_region(&self, region: Region, edit: RegionEdit) {
+        self.send_command(SimulationCommand::EditRegion { region, edit });
+    }
+
+    /// Groups the following edits into one undo step, until [`Self::end_stroke`].
+    pub fn begin_stroke(&self) {
+        self.send_command(SimulationCommand::BeginStroke);
+    }
+
+    pub fn end_stroke(&self) {
+        self.send_command(SimulationCommand::EndStroke);
+    }
+
+    pub fn undo(&self) {
+        self.send_command(SimulationCommand::Undo);
+    }
+
+    pub fn redo(&self) {
+        self.send_command(SimulationCommand::Redo);
+    }
+
+    pub fn start_stats_export(&self, path: impl Into<PathBuf>) {
+        self.send_command(SimulationCommand::StartStatsExport { path: path.into() });
+    }
+
+    pub fn stop_stats_export(&self) {
+        self.send_command(SimulationCommand::StopStatsExport);
+    }
+
+    pub fn start_frame_recording(
+        &self,
+        directory: impl Into<PathBuf>,
+        every_n_steps: u64,
+        scale: u32,
+    ) {
+        self.send_command(SimulationCommand::StartFrameRecording {
+            directory: directory.into(),
+            every_n_steps,
+            scale,
+        });
+    }
+
+    pub fn stop_frame_recording(&self) {
+        self.send_command(SimulationCommand::StopFrameRecording);
+    }
+
+    pub fn start_animation_recording(
+        &self,
+        path: impl Into<PathBuf>,
+        format: AnimationFormat,
+        settings: AnimationSettings,
+    ) {
+        self.send_command(SimulationCommand::StartAnimationRecording {
+            path: path.into(),
+            format,
+            settings,
+        });
+    }
+
+    pub fn stop_animation_recording(&self) {
+        self.send_command(SimulationCommand::StopAnimationRecording);
+    }
+
+    /// Saves every world edit since the simulation started, see [`ReplayLog`].
+    pub fn save_replay(&self, path: impl Into<PathBuf>) {
+        self.send_command(SimulationCommand::SaveReplay { path: path.into() });
+    }
+
+    /// Restarts from the replay's initial state and plays back its recorded edits.
+    pub fn play_replay(&self, path: impl Into<PathBuf>) {
+        self.send_command(SimulationCommand::PlayReplay { path: path.into() });
+    }
+
+    /// Jumps back to `step`, as long as it is not older than the oldest checkpoint.
+    pub fn rewind_to(&self, step: u64) {
+        self.send_command(SimulationCommand::RewindTo { step });
+    }
+
+    pub fn set_checkpoint_settings(&self, settings: CheckpointSettings) {
+        self.send_command(SimulationCommand::SetCheckpointSettings { settings });
+    }
+
     pub fn inspect_cell(&self, x: u16, y: u16) {
         self.send_command(SimulationCommand::Inspect { x, y });
     }
+
+    /// Selects the ants within the region, replying with their [`SimulationEvent::SelectionStats`].
+    pub fn select_ants(&self, region: Region) {
+        self.send_command(SimulationCommand::SelectAnts { region });
+    }
+
+    pub fn clear_selection(&self) {
+        self.send_command(SimulationCommand::ClearSelection);
+    }
+
+    /// Requests fresh [`SimulationEvent::SelectionStats`] for the current selection.
+    pub fn inspect_selection(&self) {
+        self.send_command(SimulationCommand::InspectSelection);
+    }
+
+    /// Applies `edit` to every selected ant, as a single undoable edit.
+    pub fn edit_selection(&self, edit: AntGroupEdit) {
+        self.send_command(SimulationCommand::EditSelection { edit });
+    }
 }
 
 impl Drop for ThreadedSimulation {
@@ -1,7 +1,12 @@
 use crate::simulation::ant::{Ant, AntSenses};
+use crate::threaded::ant_history::AntRecord;
 
 #[derive(Clone)]
 pub struct AntBuffer {
     pub ant: Ant,
     pub senses: AntSenses,
+    /// Left, forward and right sensor positions.
+    pub sensors: [(f32, f32); 3],
+    /// The ant's most recent steps, oldest first.
+    pub history: Vec<AntRecord>,
 }
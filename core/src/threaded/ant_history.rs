@@ -0,0 +1,78 @@
+use crate::simulation::ant::{Ant, AntMode, AntSenses};
+use std::collections::VecDeque;
+use std::f32::consts::{PI, TAU};
+
+/// How many steps of the inspected ant are kept.
+const HISTORY_LENGTH: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoodEvent {
+    PickedUp,
+    Delivered,
+}
+
+/// What the inspected ant sensed and did during a single step.
+#[derive(Debug, Clone, Copy)]
+pub struct AntRecord {
+    pub step: u64,
+    /// Position after the step.
+    pub x: f32,
+    pub y: f32,
+    /// Mode after the step.
+    pub mode: AntMode,
+    /// What the ant sensed before deciding on its turn.
+    pub senses: AntSenses,
+    /// The turn the ant actually made, in radians within `-PI..=PI`.
+    pub turn: f32,
+    pub food_event: Option<FoodEvent>,
+}
+
+/// Records the inspected ant step by step, starting over whenever another ant is inspected.
+#[derive(Default)]
+pub struct AntHistory {
+    ant: Option<u16>,
+    records: VecDeque<AntRecord>,
+}
+
+impl AntHistory {
+    /// The records of the given ant, oldest first, or nothing if another ant is being recorded.
+    pub fn records_of(&self, ant: u16) -> Vec<AntRecord> {
+        if self.ant == Some(ant) {
+            self.records.iter().copied().collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Records a step of `index`, given the ant and its senses before and the ant after it.
+    pub fn record(&mut self, index: u16, before: &Ant, senses: AntSenses, after: &Ant, step: u64) {
+        if self.ant != Some(index) {
+            self.clear();
+            self.ant = Some(index);
+        }
+
+        let food_event = match (before.has_food, after.has_food) {
+            (false, true) => Some(FoodEvent::PickedUp),
+            (true, false) => Some(FoodEvent::Delivered),
+            _ => None,
+        };
+
+        if self.records.len() == HISTORY_LENGTH {
+            self.records.pop_front();
+        }
+        self.records.push_back(AntRecord {
+            step,
+            x: after.x,
+            y: after.y,
+            mode: after.mode,
+            senses,
+            turn: (after.angle - before.angle + PI).rem_euclid(TAU) - PI,
+            food_event,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.ant = None;
+        self.records.clear();
+    }
+}
@@ -1,8 +1,85 @@
+use crate::checkpoint::CheckpointSettings;
+use crate::export::animation::{AnimationFormat, AnimationSettings};
+use crate::simulation::display::DisplaySettings;
+use crate::simulation::group::AntGroupEdit;
+use crate::simulation::region::{Region, RegionEdit};
+use crate::simulation::settings::SettingsPatch;
+use std::path::PathBuf;
+
 pub enum SimulationCommand {
     Clear,
     Shutdown,
-    Inspect { x: u16, y: u16 },
-    SpawnAnt { x: u16, y: u16, tribe: u8 },
-    SpawnNest { x: u16, y: u16, tribe: u8 },
-    SpawnFood { x: u16, y: u16, amount: u8 },
+    Inspect {
+        x: u16,
+        y: u16,
+    },
+    SpawnAnt {
+        x: u16,
+        y: u16,
+        tribe: u8,
+    },
+    SpawnNest {
+        x: u16,
+        y: u16,
+        tribe: u8,
+    },
+    SpawnFood {
+        x: u16,
+        y: u16,
+        amount: u8,
+    },
+    EditRegion {
+        region: Region,
+        edit: RegionEdit,
+    },
+    SelectAnts {
+        region: Region,
+    },
+    ClearSelection,
+    InspectSelection,
+    EditSelection {
+        edit: AntGroupEdit,
+    },
+    BeginStroke,
+    EndStroke,
+    Undo,
+    Redo,
+    StartStatsExport {
+        path: PathBuf,
+    },
+    StopStatsExport,
+    StartFrameRecording {
+        directory: PathBuf,
+        every_n_steps: u64,
+        scale: u32,
+    },
+    StopFrameRecording,
+    StartAnimationRecording {
+        path: PathBuf,
+        format: AnimationFormat,
+        settings: AnimationSettings,
+    },
+    StopAnimationRecording,
+    SaveReplay {
+        path: PathBuf,
+    },
+    PlayReplay {
+        path: PathBuf,
+    },
+    UpdateSettings {
+        patch: SettingsPatch,
+    },
+    /// Changes how the world is drawn, without being recorded for replays.
+    SetDisplaySettings {
+        settings: DisplaySettings,
+    },
+    Step {
+        count: u64,
+    },
+    RewindTo {
+        step: u64,
+    },
+    SetCheckpointSettings {
+        settings: CheckpointSettings,
+    },
 }
@@ -1,27 +1,64 @@
+use crate::checkpoint::{CheckpointRing, CheckpointSettings};
+use crate::export::animation::{AnimationFormat, AnimationRecorder, AnimationSettings};
+use crate::export::frame::FrameRecorder;
+use crate::export::stats::StatsExporter;
+use crate::replay::{ReplayAction, ReplayLog, ReplayPlayer};
+use crate::simulation::ant::{Ant, AntSenses};
+use crate::simulation::food::FoodPatch;
+use crate::simulation::group::AntGroupEdit;
+use crate::simulation::revert::Revert;
+use crate::simulation::settings::SimulationSettings;
+use crate::simulation::stats::SimulationStats;
 use crate::simulation::Simulation;
 use crate::threaded::ant_buffer::AntBuffer;
+use crate::threaded::ant_history::AntHistory;
 use crate::threaded::command::SimulationCommand;
 use crate::threaded::event::{InspectedCell, SimulationEvent};
+use crate::threaded::history::EditHistory;
 use crate::threaded::shared::SharedState;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{Receiver, Sender};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// While fast-forwarding, frames and settings are only published this often.
+const DISPLAY_INTERVAL: Duration = Duration::from_micros(16_667);
 
 pub struct ThreadedContext {
     pub simulation: Simulation,
     pub command_rx: Receiver<SimulationCommand>,
     pub event_tx: Sender<SimulationEvent>,
+    /// Receives the stats of every step, see [`crate::threaded::stats_history::StatsHistory`].
+    pub stats_tx: Sender<SimulationStats>,
     pub shared: Arc<SharedState>,
     pub frame_writer: triple_buffer::Input<Vec<u8>>,
     pub ant_writer: triple_buffer::Input<Option<AntBuffer>>,
+    pub settings_writer: triple_buffer::Input<SimulationSettings>,
+    pub food_patches_writer: triple_buffer::Input<Vec<FoodPatch>>,
+    pub stats_exporter: Option<StatsExporter>,
+    pub frame_recorder: Option<FrameRecorder>,
+    pub animation_recorder: Option<AnimationRecorder>,
+    pub replay_log: ReplayLog,
+    pub replay_player: Option<ReplayPlayer>,
+    pub checkpoints: CheckpointRing,
+    /// Steps still to run while paused, queued by [`SimulationCommand::Step`].
+    pub pending_steps: u64,
+    pub history: EditHistory,
+    pub ant_history: AntHistory,
 }
 
 impl ThreadedContext {
     pub fn run(mut self) {
         let mut running = true;
 
+        // Always keep the starting point around, so the whole run can be rewound
+        self.capture_checkpoint(true);
+
+        let mut last_publish = Instant::now();
+
         while running {
-            let frame_start = std::time::Instant::now();
+            let frame_start = Instant::now();
 
             self.sync_state();
 
@@ -31,9 +68,30 @@ impl ThreadedContext {
                 }
             }
 
-            self.simulation.step();
-            self.sync_frame();
-            self.sync_ant();
+            let settings = self.simulation.settings();
+            let fast_forward = settings.fast_forward;
+            let steps = if fast_forward {
+                1
+            } else {
+                settings.speed_multiplier.max(1)
+            };
+
+            for _ in 0..steps {
+                self.advance();
+            }
+
+            let unthrottled =
+                fast_forward && (!self.simulation.settings().paused || self.pending_steps > 0);
+            if !unthrottled || last_publish.elapsed() >= DISPLAY_INTERVAL {
+                self.sync_settings();
+                self.sync_frame();
+                self.sync_ant();
+                last_publish = Instant::now();
+            }
+
+            if unthrottled {
+                continue;
+            }
 
             let elapsed = frame_start.elapsed();
             let target = Duration::from_secs_f64(1.0 / self.shared.steps_per_second() as f64);
@@ -42,6 +100,8 @@ impl ThreadedContext {
                 std::thread::sleep(remaining);
             }
         }
+
+        self.finish_animation_recording();
     }
 
     fn sync_state(&mut self) {
@@ -49,9 +109,83 @@ impl ThreadedContext {
         self.shared.sync_stats(self.simulation.stats());
     }
 
+    fn sync_settings(&mut self) {
+        self.settings_writer
+            .input_buffer_mut()
+            .clone_from(self.simulation.settings());
+        self.settings_writer.publish();
+    }
+
+    /// Runs a single step, together with everything that happens per step.
+    fn advance(&mut self) {
+        self.play_replay_actions();
+
+        let previous_step = self.simulation.stats().step;
+        let inspected = self.observe_inspected_ant();
+        self.step_simulation();
+        if self.simulation.stats().step != previous_step {
+            self.export_stats();
+            let _ = self.stats_tx.send(self.simulation.stats().clone());
+            self.record_frame();
+            self.record_animation();
+            self.capture_checkpoint(false);
+            self.record_ant_history(inspected);
+        }
+    }
+
+    /// The inspected ant and what it senses, as it is about to step.
+    fn observe_inspected_ant(&self) -> Option<(u16, Ant, AntSenses)> {
+        let index = self.simulation.settings().inspected_ant?;
+        let ant = self.simulation.get_ant(index as usize)?;
+        let senses = self.simulation.sense_for_ant(&ant);
+        Some((index, ant, senses))
+    }
+
+    fn record_ant_history(&mut self, inspected: Option<(u16, Ant, AntSenses)>) {
+        let Some((index, before, senses)) = inspected else {
+            self.ant_history.clear();
+            return;
+        };
+
+        // The ant may have been removed, or another one inspected, during the step
+        if self.simulation.settings().inspected_ant != Some(index) {
+            self.ant_history.clear();
+            return;
+        }
+
+        if let Some(after) = self.simulation.get_ant(index as usize) {
+            let step = self.simulation.stats().step;
+            self.ant_history
+                .record(index, &before, senses, &after, step);
+        }
+    }
+
+    fn step_simulation(&mut self) {
+        // Resuming drops whatever is left of a queued run
+        if !self.simulation.settings().paused {
+            self.pending_steps = 0;
+            self.simulation.step();
+            return;
+        }
+
+        if self.pending_steps == 0 {
+            return;
+        }
+
+        self.pending_steps -= 1;
+        self.simulation.settings_mut().paused = false;
+        self.simulation.step();
+        self.simulation.settings_mut().paused = true;
+    }
+
     fn sync_frame(&mut self) {
         self.simulation.draw(self.frame_writer.input_buffer_mut());
         self.frame_writer.publish();
+
+        if self.shared.wants_food_patches() {
+            *self.food_patches_writer.input_buffer_mut() = self.simulation.food_patches();
+            self.food_patches_writer.publish();
+        }
     }
 
     fn sync_ant(&mut self) {
@@ -59,7 +193,13 @@ impl ThreadedContext {
             && let Some(ant) = self.simulation.get_ant(index as usize)
         {
             let senses = self.simulation.sense_for_ant(&ant);
-            let ant_buffer = AntBuffer { ant, senses };
+            let sensors = self.simulation.sensor_positions(&ant);
+            let ant_buffer = AntBuffer {
+                ant,
+                senses,
+                sensors,
+                history: self.ant_history.records_of(index),
+            };
             *self.ant_writer.input_buffer_mut() = Some(ant_buffer);
             self.ant_writer.publish();
         }
@@ -68,18 +208,254 @@ impl ThreadedContext {
     pub fn handle_command(&mut self, command: SimulationCommand) -> bool {
         let mut do_continue = true;
         match command {
-            SimulationCommand::Clear => self.simulation.clear(),
+            SimulationCommand::Clear => self.apply_action(ReplayAction::Clear),
             SimulationCommand::Inspect { x, y } => self.inspect(x, y),
             SimulationCommand::Shutdown => do_continue = false,
-            SimulationCommand::SpawnAnt { x, y, tribe } => self.simulation.spawn_ant(x, y, tribe),
-            SimulationCommand::SpawnNest { x, y, tribe } => self.simulation.spawn_nest(x, y, tribe),
+            SimulationCommand::SpawnAnt { x, y, tribe } => {
+                self.apply_action(ReplayAction::SpawnAnt { x, y, tribe })
+            }
+            SimulationCommand::SpawnNest { x, y, tribe } => {
+                self.apply_action(ReplayAction::SpawnNest { x, y, tribe })
+            }
             SimulationCommand::SpawnFood { x, y, amount } => {
-                self.simulation.spawn_food(x, y, amount)
+                self.apply_action(ReplayAction::SpawnFood { x, y, amount })
+            }
+            SimulationCommand::EditRegion { region, edit } => {
+                self.apply_action(ReplayAction::EditRegion { region, edit })
+            }
+            SimulationCommand::BeginStroke => self.history.begin_stroke(),
+            SimulationCommand::EndStroke => {
+                self.history.end_stroke();
+                self.shared.sync_history(&self.history);
+            }
+            SimulationCommand::Undo => self.undo(),
+            SimulationCommand::Redo => self.redo(),
+            SimulationCommand::SelectAnts { region } => {
+                self.simulation.select_ants(&region);
+                self.inspect_selection();
+            }
+            SimulationCommand::ClearSelection => {
+                self.simulation.clear_selection();
+                self.inspect_selection();
+            }
+            SimulationCommand::InspectSelection => self.inspect_selection(),
+            SimulationCommand::EditSelection { edit } => self.edit_selection(edit),
+            SimulationCommand::StartStatsExport { path } => self.start_stats_export(&path),
+            SimulationCommand::StopStatsExport => self.stop_stats_export(),
+            SimulationCommand::StartFrameRecording {
+                directory,
+                every_n_steps,
+                scale,
+            } => self.start_frame_recording(directory, every_n_steps, scale),
+            SimulationCommand::StopFrameRecording => self.frame_recorder = None,
+            SimulationCommand::StartAnimationRecording {
+                path,
+                format,
+                settings,
+            } => self.start_animation_recording(path, format, settings),
+            SimulationCommand::StopAnimationRecording => self.finish_animation_recording(),
+            SimulationCommand::SaveReplay { path } => self.save_replay(path),
+            SimulationCommand::PlayReplay { path } => self.play_replay(&path),
+            SimulationCommand::UpdateSettings { patch } => {
+                if !patch.is_empty() {
+                    self.apply_action(ReplayAction::UpdateSettings(patch))
+                }
+            }
+            SimulationCommand::SetDisplaySettings { settings } => {
+                self.simulation.settings_mut().display = settings
+            }
+            SimulationCommand::Step { count } => self.queue_steps(count),
+            SimulationCommand::RewindTo { step } => self.rewind_to(step),
+            SimulationCommand::SetCheckpointSettings { settings } => {
+                self.set_checkpoint_settings(settings)
             }
         }
         do_continue
     }
 
+    /// Applies a world edit and records it for replays and undo.
+    fn apply_action(&mut self, action: ReplayAction) {
+        if let Some(revert) = self.apply_recorded(action.clone()) {
+            self.history.record(action, revert);
+            self.shared.sync_history(&self.history);
+        }
+    }
+
+    fn apply_recorded(&mut self, action: ReplayAction) -> Option<Revert> {
+        let revert = action.apply_reversible(&mut self.simulation);
+        self.replay_log.record(self.simulation.stats().step, action);
+        revert
+    }
+
+    fn undo(&mut self) {
+        let Some(edit) = self.history.pop_undo() else {
+            return;
+        };
+
+        // Undone in reverse, since every revert expects the world as its edit left it
+        for revert in edit.reverts.into_iter().rev() {
+            self.apply_recorded(ReplayAction::Revert(Box::new(revert)));
+        }
+        self.history.push_redo(edit.actions);
+        self.shared.sync_history(&self.history);
+    }
+
+    fn redo(&mut self) {
+        let Some(actions) = self.history.pop_redo() else {
+            return;
+        };
+
+        self.history.begin_stroke();
+        for action in actions {
+            if let Some(revert) = self.apply_recorded(action.clone()) {
+                self.history.record_redone(action, revert);
+            }
+        }
+        self.history.end_stroke();
+        self.shared.sync_history(&self.history);
+    }
+
+    fn play_replay_actions(&mut self) {
+        let Some(player) = &mut self.replay_player else {
+            return;
+        };
+
+        let actions = player.take_due(self.simulation.stats().step);
+        if player.is_finished() {
+            self.replay_player = None;
+        }
+
+        for action in actions {
+            self.apply_action(action);
+        }
+    }
+
+    fn save_replay(&mut self, path: PathBuf) {
+        self.replay_log.set_end_step(self.simulation.stats().step);
+        match self.replay_log.save(&path) {
+            Ok(()) => self.send_event(SimulationEvent::ReplaySaved(path)),
+            Err(err) => self.send_event(SimulationEvent::ReplayFailed(err)),
+        }
+    }
+
+    fn play_replay(&mut self, path: &Path) {
+        let log = match ReplayLog::load(path) {
+            Ok(log) => log,
+            Err(err) => {
+                self.send_event(SimulationEvent::ReplayFailed(err));
+                return;
+            }
+        };
+
+        // The frame buffers are sized once, so only replays of equally sized worlds can be played
+        let settings = self.simulation.settings();
+        let initial = &log.initial.settings;
+        if initial.width != settings.width
+            || initial.height != settings.height
+            || initial.tribe_count != settings.tribe_count
+        {
+            let err = io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "replay was recorded in a {}x{} world with {} tribes",
+                    initial.width, initial.height, initial.tribe_count
+                ),
+            );
+            self.send_event(SimulationEvent::ReplayFailed(err));
+            return;
+        }
+
+        // How the world is drawn is up to the viewer, not part of what is being replayed
+        let display = self.simulation.settings().display.clone();
+        self.simulation = Simulation::from_snapshot(log.initial.clone());
+        self.simulation.settings_mut().inspected_ant = None;
+        self.simulation.settings_mut().display = display;
+        self.replay_log = ReplayLog::new(self.simulation.snapshot());
+        self.replay_player = Some(log.player());
+
+        self.checkpoints.clear();
+        self.capture_checkpoint(true);
+        self.history.clear();
+        self.ant_history.clear();
+        self.shared.sync_history(&self.history);
+    }
+
+    fn capture_checkpoint(&mut self, force: bool) {
+        let result = if force {
+            self.checkpoints.capture(&self.simulation).map(|_| true)
+        } else {
+            self.checkpoints.maybe_capture(&self.simulation)
+        };
+
+        match result {
+            Ok(true) => self.shared.sync_checkpoints(&self.checkpoints),
+            Ok(false) => {}
+            Err(err) => self.send_event(SimulationEvent::CheckpointFailed(err)),
+        }
+    }
+
+    /// Pauses the simulation and runs `count` more steps at the current speed, so up to
+    /// `speed_multiplier` per tick, or unthrottled while fast-forwarding.
+    fn queue_steps(&mut self, count: u64) {
+        self.shared.set_paused(true);
+        self.simulation.settings_mut().paused = true;
+        self.pending_steps = self.pending_steps.saturating_add(count);
+    }
+
+    fn set_checkpoint_settings(&mut self, settings: CheckpointSettings) {
+        self.checkpoints.set_settings(settings);
+        self.shared.sync_checkpoints(&self.checkpoints);
+    }
+
+    /// Restores the closest earlier checkpoint and steps forward to exactly `step`, re-applying
+    /// the recorded world edits on the way. Everything after `step` is discarded.
+    fn rewind_to(&mut self, step: u64) {
+        if step >= self.simulation.stats().step {
+            return;
+        }
+
+        let snapshot = match self.checkpoints.latest_at_or_before(step) {
+            Ok(Some(snapshot)) => snapshot,
+            Ok(None) => {
+                self.send_event(SimulationEvent::RewindUnavailable { step });
+                return;
+            }
+            Err(err) => {
+                self.send_event(SimulationEvent::CheckpointFailed(err));
+                return;
+            }
+        };
+
+        let mut simulation = Simulation::from_snapshot(snapshot);
+        simulation.settings_mut().paused = false;
+
+        let mut player = self.replay_log.player_from(simulation.stats().step);
+        while simulation.stats().step < step {
+            player.apply_due(&mut simulation);
+            simulation.step();
+        }
+
+        let settings = self.simulation.settings();
+        simulation.settings_mut().paused = settings.paused;
+        simulation.settings_mut().inspected_ant = settings.inspected_ant;
+        simulation.settings_mut().display = settings.display.clone();
+
+        // A replay being played back would continue from where it left off, not from the rewound step
+        self.replay_player = None;
+
+        let step = simulation.stats().step;
+        self.simulation = simulation;
+        self.replay_log.truncate(step);
+        self.checkpoints.truncate_after(step);
+        self.shared.sync_checkpoints(&self.checkpoints);
+
+        // Reverts only apply to the world they were captured in
+        self.history.clear();
+        self.ant_history.clear();
+        self.shared.sync_history(&self.history);
+        self.shared.sync_stats(self.simulation.stats());
+    }
+
     fn inspect(&mut self, x: u16, y: u16) {
         let cell = self.simulation.get_cell(x, y);
         let inspected_cell = InspectedCell { x, y, cell };
@@ -90,6 +466,107 @@ impl ThreadedContext {
         }
     }
 
+    fn inspect_selection(&mut self) {
+        let stats = self.simulation.group_stats(self.simulation.selected_ants());
+        self.send_event(SimulationEvent::SelectionStats(Box::new(stats)));
+    }
+
+    fn edit_selection(&mut self, edit: AntGroupEdit) {
+        let ants = self.simulation.selected_ants().to_vec();
+        if !ants.is_empty() {
+            self.apply_action(ReplayAction::EditAnts { ants, edit });
+        }
+        self.inspect_selection();
+    }
+
+    fn start_stats_export(&mut self, path: &Path) {
+        self.stop_stats_export();
+        match StatsExporter::create_from_path(path) {
+            Ok(exporter) => self.stats_exporter = Some(exporter),
+            Err(err) => self.send_event(SimulationEvent::StatsExportFailed(err)),
+        }
+    }
+
+    fn stop_stats_export(&mut self) {
+        if let Some(mut exporter) = self.stats_exporter.take()
+            && let Err(err) = exporter.flush()
+        {
+            self.send_event(SimulationEvent::StatsExportFailed(err));
+        }
+    }
+
+    fn export_stats(&mut self) {
+        let Some(exporter) = &mut self.stats_exporter else {
+            return;
+        };
+
+        if let Err(err) = exporter.write(self.simulation.stats()) {
+            self.stats_exporter = None;
+            self.send_event(SimulationEvent::StatsExportFailed(err));
+        }
+    }
+
+    fn start_frame_recording(&mut self, directory: PathBuf, every_n_steps: u64, scale: u32) {
+        match FrameRecorder::new(directory, every_n_steps, scale) {
+            Ok(recorder) => self.frame_recorder = Some(recorder),
+            Err(err) => self.send_event(SimulationEvent::FrameRecordingFailed(err)),
+        }
+    }
+
+    fn record_frame(&mut self) {
+        let Some(recorder) = &mut self.frame_recorder else {
+            return;
+        };
+
+        if let Err(err) = recorder.record(&self.simulation) {
+            self.frame_recorder = None;
+            self.send_event(SimulationEvent::FrameRecordingFailed(err));
+        }
+    }
+
+    fn start_animation_recording(
+        &mut self,
+        path: PathBuf,
+        format: AnimationFormat,
+        settings: AnimationSettings,
+    ) {
+        self.finish_animation_recording();
+
+        let width = self.simulation.settings().width;
+        let height = self.simulation.settings().height;
+        match AnimationRecorder::create(path, format, settings, width, height) {
+            Ok(recorder) => self.animation_recorder = Some(recorder),
+            Err(err) => self.send_event(SimulationEvent::AnimationRecordingFailed(err)),
+        }
+    }
+
+    fn record_animation(&mut self) {
+        let Some(recorder) = &mut self.animation_recorder else {
+            return;
+        };
+
+        match recorder.record(&self.simulation) {
+            Ok(_) if recorder.is_full() => self.finish_animation_recording(),
+            Ok(_) => {}
+            Err(err) => {
+                self.animation_recorder = None;
+                self.send_event(SimulationEvent::AnimationRecordingFailed(err));
+            }
+        }
+    }
+
+    fn finish_animation_recording(&mut self) {
+        let Some(recorder) = self.animation_recorder.take() else {
+            return;
+        };
+
+        let path = recorder.path().to_path_buf();
+        match recorder.finish() {
+            Ok(()) => self.send_event(SimulationEvent::AnimationRecordingFinished(path)),
+            Err(err) => self.send_event(SimulationEvent::AnimationRecordingFailed(err)),
+        }
+    }
+
     fn send_event(&self, event: SimulationEvent) {
         let _ = self.event_tx.send(event);
     }
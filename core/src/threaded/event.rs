@@ -1,7 +1,22 @@
 use crate::simulation::cell::Cell;
+use crate::simulation::group::AntGroupStats;
+use std::io;
+use std::path::PathBuf;
 
 pub enum SimulationEvent {
     InspectedCell(Box<InspectedCell>),
+    SelectionStats(Box<AntGroupStats>),
+    StatsExportFailed(io::Error),
+    FrameRecordingFailed(io::Error),
+    AnimationRecordingFinished(PathBuf),
+    AnimationRecordingFailed(io::Error),
+    ReplaySaved(PathBuf),
+    ReplayFailed(io::Error),
+    CheckpointFailed(io::Error),
+    /// No checkpoint is old enough to rewind to the requested step.
+    RewindUnavailable {
+        step: u64,
+    },
 }
 
 #[derive(Debug, Default)]
@@ -0,0 +1,101 @@
+use crate::replay::ReplayAction;
+use crate::simulation::revert::Revert;
+use std::collections::VecDeque;
+
+/// How many edits can be undone, older ones are forgotten.
+const MAX_UNDO_STEPS: usize = 100;
+
+/// One undoable step, either a single edit or a whole brush stroke.
+#[derive(Default)]
+pub struct Edit {
+    pub actions: Vec<ReplayAction>,
+    pub reverts: Vec<Revert>,
+}
+
+impl Edit {
+    fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+#[derive(Default)]
+pub struct EditHistory {
+    undo: VecDeque<Edit>,
+    redo: Vec<Vec<ReplayAction>>,
+    stroke: Option<Edit>,
+}
+
+impl EditHistory {
+    /// Records an applied world edit, which invalidates everything that could be redone.
+    pub fn record(&mut self, action: ReplayAction, revert: Revert) {
+        self.redo.clear();
+        self.push(action, revert);
+    }
+
+    /// Records an edit re-applied by a redo, keeping the remaining redo steps.
+    pub fn record_redone(&mut self, action: ReplayAction, revert: Revert) {
+        self.push(action, revert);
+    }
+
+    fn push(&mut self, action: ReplayAction, revert: Revert) {
+        if let Some(stroke) = &mut self.stroke {
+            stroke.actions.push(action);
+            stroke.reverts.push(revert);
+            return;
+        }
+
+        self.push_edit(Edit {
+            actions: vec![action],
+            reverts: vec![revert],
+        });
+    }
+
+    fn push_edit(&mut self, edit: Edit) {
+        if self.undo.len() == MAX_UNDO_STEPS {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(edit);
+    }
+
+    /// Groups all edits until [`Self::end_stroke`] into one undo step.
+    pub fn begin_stroke(&mut self) {
+        self.end_stroke();
+        self.stroke = Some(Edit::default());
+    }
+
+    pub fn end_stroke(&mut self) {
+        if let Some(stroke) = self.stroke.take()
+            && !stroke.is_empty()
+        {
+            self.push_edit(stroke);
+        }
+    }
+
+    pub fn pop_undo(&mut self) -> Option<Edit> {
+        self.end_stroke();
+        self.undo.pop_back()
+    }
+
+    pub fn push_redo(&mut self, actions: Vec<ReplayAction>) {
+        self.redo.push(actions);
+    }
+
+    pub fn pop_redo(&mut self) -> Option<Vec<ReplayAction>> {
+        self.end_stroke();
+        self.redo.pop()
+    }
+
+    pub fn undo_count(&self) -> usize {
+        self.undo.len() + self.stroke.as_ref().is_some_and(|s| !s.is_empty()) as usize
+    }
+
+    pub fn redo_count(&self) -> usize {
+        self.redo.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.stroke = None;
+    }
+}
@@ -1,6 +1,8 @@
+use crate::checkpoint::CheckpointRing;
 use crate::simulation::pheromones::PheromoneType;
 use crate::simulation::settings::SimulationSettings;
 use crate::simulation::stats::SimulationStats;
+use crate::threaded::history::EditHistory;
 use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicU64, AtomicU8, Ordering};
 
 pub struct SharedState {
@@ -9,14 +11,23 @@ pub struct SharedState {
     ants_with_food: AtomicU16,
     total_food: AtomicU64,
     avg_step_duration_secs: AtomicU32,
+    step: AtomicU64,
+    // Checkpoints
+    oldest_checkpoint_step: AtomicU64,
+    checkpoint_count: AtomicU32,
+    checkpoint_bytes: AtomicU64,
+    // Edit history
+    undo_count: AtomicU32,
+    redo_count: AtomicU32,
     // Settings
     is_paused: AtomicBool,
     steps_per_second: AtomicU8,
-    pheromone_decay: AtomicU32,
+    speed_multiplier: AtomicU16,
+    fast_forward: AtomicBool,
     drawn_pheromone: AtomicU8,
-    drawn_pheromone_tribe: AtomicU8,
     inspected_ant: AtomicU32,
     tribe_count: AtomicU8,
+    wants_food_patches: AtomicBool,
 }
 
 impl SharedState {
@@ -26,24 +37,31 @@ impl SharedState {
             ants_with_food: AtomicU16::new(0),
             total_food: AtomicU64::new(0),
             avg_step_duration_secs: AtomicU32::new(0),
+            step: AtomicU64::new(0),
+            oldest_checkpoint_step: AtomicU64::new(u64::MAX),
+            checkpoint_count: AtomicU32::new(0),
+            checkpoint_bytes: AtomicU64::new(0),
+            undo_count: AtomicU32::new(0),
+            redo_count: AtomicU32::new(0),
             is_paused: AtomicBool::new(settings.paused),
             steps_per_second: AtomicU8::new(settings.steps_per_second),
-            pheromone_decay: AtomicU32::new(settings.pheromone_decay.to_bits()),
+            speed_multiplier: AtomicU16::new(settings.speed_multiplier),
+            fast_forward: AtomicBool::new(settings.fast_forward),
             drawn_pheromone: AtomicU8::new(
                 settings.drawn_pheromone.map(|p| p as u8).unwrap_or(255),
             ),
-            drawn_pheromone_tribe: AtomicU8::new(settings.drawn_pheromone_tribe),
             inspected_ant: AtomicU32::new(0b1_00000000_00000000),
             tribe_count: AtomicU8::new(settings.tribe_count),
+            wants_food_patches: AtomicBool::new(false),
         }
     }
 
     pub fn sync_settings(&self, settings: &mut SimulationSettings) {
         settings.paused = self.is_paused();
         settings.steps_per_second = self.steps_per_second();
-        settings.pheromone_decay = self.pheromone_decay();
+        settings.speed_multiplier = self.speed_multiplier();
+        settings.fast_forward = self.is_fast_forward();
         settings.drawn_pheromone = self.drawn_pheromone();
-        settings.drawn_pheromone_tribe = self.drawn_pheromone_tribe();
         self.set_inspected_ant(settings.inspected_ant);
     }
 
@@ -52,6 +70,25 @@ impl SharedState {
         self.set_ants_with_food(stats.ants_with_food);
         self.set_total_food(stats.total_food);
         self.set_avg_step_duration_secs(stats.avg_step_duration_secs);
+        self.step.store(stats.step, Ordering::Relaxed);
+    }
+
+    pub fn sync_checkpoints(&self, checkpoints: &CheckpointRing) {
+        self.oldest_checkpoint_step.store(
+            checkpoints.oldest_step().unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        self.checkpoint_count
+            .store(checkpoints.len() as u32, Ordering::Relaxed);
+        self.checkpoint_bytes
+            .store(checkpoints.used_bytes() as u64, Ordering::Relaxed);
+    }
+
+    pub fn sync_history(&self, history: &EditHistory) {
+        self.undo_count
+            .store(history.undo_count() as u32, Ordering::Relaxed);
+        self.redo_count
+            .store(history.redo_count() as u32, Ordering::Relaxed);
     }
 
     pub fn ant_count(&self) -> u16 {
@@ -87,6 +124,32 @@ impl SharedState {
             .store(avg_step_duration_secs.to_bits(), Ordering::Relaxed);
     }
 
+    pub fn step(&self) -> u64 {
+        self.step.load(Ordering::Relaxed)
+    }
+
+    /// The earliest step the simulation can be rewound to.
+    pub fn oldest_checkpoint_step(&self) -> Option<u64> {
+        let step = self.oldest_checkpoint_step.load(Ordering::Relaxed);
+        if step == u64::MAX { None } else { Some(step) }
+    }
+
+    pub fn checkpoint_count(&self) -> u32 {
+        self.checkpoint_count.load(Ordering::Relaxed)
+    }
+
+    pub fn checkpoint_bytes(&self) -> u64 {
+        self.checkpoint_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.undo_count.load(Ordering::Relaxed) > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.redo_count.load(Ordering::Relaxed) > 0
+    }
+
     pub fn is_paused(&self) -> bool {
         self.is_paused.load(Ordering::Relaxed)
     }
@@ -104,13 +167,21 @@ impl SharedState {
             .store(steps_per_second, Ordering::Relaxed);
     }
 
-    pub fn pheromone_decay(&self) -> f32 {
-        f32::from_bits(self.pheromone_decay.load(Ordering::Relaxed))
+    pub fn speed_multiplier(&self) -> u16 {
+        self.speed_multiplier.load(Ordering::Relaxed)
+    }
+
+    pub fn set_speed_multiplier(&self, speed_multiplier: u16) {
+        self.speed_multiplier
+            .store(speed_multiplier.max(1), Ordering::Relaxed);
+    }
+
+    pub fn is_fast_forward(&self) -> bool {
+        self.fast_forward.load(Ordering::Relaxed)
     }
 
-    pub fn set_pheromone_decay(&self, decay: f32) {
-        self.pheromone_decay
-            .store(decay.to_bits(), Ordering::Relaxed);
+    pub fn set_fast_forward(&self, fast_forward: bool) {
+        self.fast_forward.store(fast_forward, Ordering::Relaxed);
     }
 
     pub fn drawn_pheromone(&self) -> Option<PheromoneType> {
@@ -122,14 +193,6 @@ impl SharedState {
             .store(pheromone.map(|p| p as u8).unwrap_or(255), Ordering::Relaxed);
     }
 
-    pub fn drawn_pheromone_tribe(&self) -> u8 {
-        self.drawn_pheromone_tribe.load(Ordering::Relaxed)
-    }
-
-    pub fn set_drawn_pheromone_tribe(&self, tribe: u8) {
-        self.drawn_pheromone_tribe.store(tribe, Ordering::Relaxed);
-    }
-
     pub fn inspected_ant(&self) -> Option<u16> {
         let index = self.inspected_ant.load(Ordering::Relaxed);
         if index > 65535 {
@@ -149,4 +212,14 @@ impl SharedState {
     pub fn tribe_count(&self) -> u8 {
         self.tribe_count.load(Ordering::Relaxed)
     }
+
+    /// Whether food patches are published with every frame, which costs a pass over the world.
+    pub fn wants_food_patches(&self) -> bool {
+        self.wants_food_patches.load(Ordering::Relaxed)
+    }
+
+    pub fn set_wants_food_patches(&self, wants_food_patches: bool) {
+        self.wants_food_patches
+            .store(wants_food_patches, Ordering::Relaxed);
+    }
 }
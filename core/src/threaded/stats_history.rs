@@ -0,0 +1,110 @@
+use crate::simulation::stats::SimulationStats;
+use std::collections::VecDeque;
+
+/// The stats of the most recent steps, oldest first.
+pub struct StatsHistory {
+    samples: VecDeque<SimulationStats>,
+    capacity: usize,
+}
+
+impl Default for StatsHistory {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl StatsHistory {
+    pub const DEFAULT_CAPACITY: usize = 20_000;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Adds the stats of a step, dropping samples of any later steps first, so rewinding or
+    /// restarting the simulation continues the history from where it went back to.
+    pub fn push(&mut self, stats: SimulationStats) {
+        while self
+            .samples
+            .back()
+            .is_some_and(|sample| sample.step >= stats.step)
+        {
+            self.samples.pop_back();
+        }
+
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(stats);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The last `count` samples, oldest first.
+    pub fn latest(&self, count: usize) -> impl Iterator<Item = &SimulationStats> {
+        self.samples
+            .range(self.samples.len().saturating_sub(count)..)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(step: u64) -> SimulationStats {
+        SimulationStats {
+            step,
+            ..Default::default()
+        }
+    }
+
+    fn steps(history: &StatsHistory) -> Vec<u64> {
+        history.latest(history.len()).map(|s| s.step).collect()
+    }
+
+    #[test]
+    fn push_drops_the_oldest_samples_beyond_capacity() {
+        let mut history = StatsHistory::new(3);
+        for step in 1..=5 {
+            history.push(stats(step));
+        }
+        assert_eq!(steps(&history), [3, 4, 5]);
+    }
+
+    #[test]
+    fn push_after_a_rewind_drops_later_samples() {
+        let mut history = StatsHistory::new(10);
+        for step in 1..=6 {
+            history.push(stats(step));
+        }
+
+        history.push(stats(3));
+        assert_eq!(steps(&history), [1, 2, 3]);
+
+        history.push(stats(0));
+        assert_eq!(steps(&history), [0]);
+    }
+
+    #[test]
+    fn latest_returns_the_newest_samples_oldest_first() {
+        let mut history = StatsHistory::new(10);
+        for step in 1..=4 {
+            history.push(stats(step));
+        }
+        let latest = history.latest(2).map(|s| s.step).collect::<Vec<_>>();
+        assert_eq!(latest, [3, 4]);
+        assert_eq!(history.latest(100).count(), 4);
+    }
+}
@@ -0,0 +1,19 @@
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::{self, Read, Write};
+
+pub fn compress<T: Serialize>(value: &T) -> io::Result<Vec<u8>> {
+    let bytes = postcard::to_stdvec(value).map_err(io::Error::other)?;
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(&bytes)?;
+    encoder.finish()
+}
+
+pub fn decompress<T: DeserializeOwned>(compressed: &[u8]) -> io::Result<T> {
+    let mut bytes = Vec::new();
+    DeflateDecoder::new(compressed).read_to_end(&mut bytes)?;
+    postcard::from_bytes(&bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
@@ -0,0 +1,308 @@
+use crate::simulation::Simulation;
+use crate::simulation::region::Region;
+use crate::simulation::settings::SimulationSettings;
+use fastrand::Rng;
+use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
+
+#[derive(Debug, Clone)]
+pub struct WorldGenSettings {
+    pub seed: u64,
+    pub nests_per_tribe: u8,
+    pub nest_radius: u16,
+    /// Minimum distance between the centers of any two nests, relaxed if the world is too crowded.
+    pub min_nest_spacing: f32,
+    pub ants_per_nest: u16,
+    /// Size of the food clusters, higher values give larger and fewer clusters.
+    pub food_noise_scale: f64,
+    /// Share of the map covered by food, between 0 and 1.
+    pub food_coverage: f32,
+    pub max_food_amount: u8,
+    /// Food is never placed closer than this to a nest center.
+    pub food_nest_clearance: f32,
+    pub caves: Option<CaveSettings>,
+}
+
+impl Default for WorldGenSettings {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            nests_per_tribe: 1,
+            nest_radius: 3,
+            min_nest_spacing: 80.0,
+            ants_per_nest: 200,
+            food_noise_scale: 40.0,
+            food_coverage: 0.05,
+            max_food_amount: 100,
+            food_nest_clearance: 40.0,
+            caves: None,
+        }
+    }
+}
+
+/// Cellular automaton carving cave-like obstacles.
+#[derive(Debug, Clone)]
+pub struct CaveSettings {
+    /// Chance of a cell starting out as a wall.
+    pub fill_probability: f32,
+    pub smoothing_iterations: u8,
+    /// Open space kept free around every nest.
+    pub nest_clearance: u16,
+}
+
+impl Default for CaveSettings {
+    fn default() -> Self {
+        Self {
+            fill_probability: 0.45,
+            smoothing_iterations: 5,
+            nest_clearance: 12,
+        }
+    }
+}
+
+/// Builds reproducible test arenas, the same seed always yields the same world.
+pub struct WorldGenerator {
+    settings: WorldGenSettings,
+}
+
+impl WorldGenerator {
+    pub fn new(settings: WorldGenSettings) -> Self {
+        Self { settings }
+    }
+
+    pub fn generate(&self, mut simulation_settings: SimulationSettings) -> Simulation {
+        // The simulation's own generator spawns the ants, it has to follow the world seed too
+        simulation_settings.seed = self.settings.seed;
+        let mut rng = Rng::with_seed(self.settings.seed);
+        let width = simulation_settings.width;
+        let height = simulation_settings.height;
+        let tribe_count = simulation_settings.tribe_count;
+
+        let nests = self.place_nests(&mut rng, width, height, tribe_count);
+        let walls = self
+            .settings
+            .caves
+            .as_ref()
+            .map(|caves| self.carve_caves(&mut rng, caves, width, height, &nests));
+
+        let mut simulation = Simulation::new(simulation_settings);
+
+        if let Some(walls) = walls {
+            for (i, is_wall) in walls.iter().enumerate() {
+                if *is_wall {
+                    simulation.spawn_wall((i % width as usize) as u16, (i / width as usize) as u16);
+                }
+            }
+        }
+
+        self.scatter_food(&mut simulation, &nests);
+
+        for (x, y, tribe) in &nests {
+            let region = Region::Circle {
+                x: *x,
+                y: *y,
+                radius: self.settings.nest_radius,
+            };
+            simulation.spawn_nest_region(&region, *tribe);
+            simulation.spawn_ants_in_region(&region, *tribe, self.settings.ants_per_nest);
+        }
+
+        simulation
+    }
+
+    fn place_nests(
+        &self,
+        rng: &mut Rng,
+        width: u16,
+        height: u16,
+        tribe_count: u8,
+    ) -> Vec<(u16, u16, u8)> {
+        const ATTEMPTS_PER_SPACING: usize = 200;
+
+        let margin = self.settings.nest_radius.min(width / 2).min(height / 2);
+        let mut spacing = self.settings.min_nest_spacing;
+        let mut nests = Vec::new();
+
+        for _ in 0..self.settings.nests_per_tribe {
+            for tribe in 0..tribe_count {
+                let mut attempts = 0;
+                loop {
+                    let x = rng.u16(margin..=width.saturating_sub(margin + 1).max(margin));
+                    let y = rng.u16(margin..=height.saturating_sub(margin + 1).max(margin));
+
+                    let fits = nests
+                        .iter()
+                        .all(|(nx, ny, _)| distance((x, y), (*nx, *ny)) >= spacing);
+                    if fits {
+                        nests.push((x, y, tribe));
+                        break;
+                    }
+
+                    attempts += 1;
+                    if attempts % ATTEMPTS_PER_SPACING == 0 {
+                        spacing *= 0.5;
+                    }
+                }
+            }
+        }
+
+        nests
+    }
+
+    fn carve_caves(
+        &self,
+        rng: &mut Rng,
+        caves: &CaveSettings,
+        width: u16,
+        height: u16,
+        nests: &[(u16, u16, u8)],
+    ) -> Vec<bool> {
+        let (width, height) = (width as i32, height as i32);
+        let index = |x: i32, y: i32| (y * width + x) as usize;
+
+        let mut walls = (0..width * height)
+            .map(|_| rng.f32() < caves.fill_probability)
+            .collect::<Vec<_>>();
+
+        for _ in 0..caves.smoothing_iterations {
+            let old = walls.clone();
+            for y in 0..height {
+                for x in 0..width {
+                    let mut neighbors = 0;
+                    for dy in -1..=1 {
+                        for dx in -1..=1 {
+                            let (nx, ny) = (x + dx, y + dy);
+                            // The world edge counts as wall, closing off the caves
+                            if nx < 0 || ny < 0 || nx >= width || ny >= height || old[index(nx, ny)]
+                            {
+                                neighbors += 1;
+                            }
+                        }
+                    }
+                    walls[index(x, y)] = neighbors >= 5;
+                }
+            }
+        }
+
+        let clearance = (caves.nest_clearance + self.settings.nest_radius) as f32;
+        for (nx, ny, _) in nests {
+            let region = Region::Circle {
+                x: *nx,
+                y: *ny,
+                radius: clearance as u16,
+            };
+            for (x, y) in region.cells(width as u16, height as u16) {
+                walls[index(x as i32, y as i32)] = false;
+            }
+        }
+
+        walls
+    }
+
+    fn scatter_food(&self, simulation: &mut Simulation, nests: &[(u16, u16, u8)]) {
+        let seed = self.settings.seed;
+        let noise = Fbm::<Perlin>::new((seed ^ (seed >> 32)) as u32).set_octaves(3);
+        let width = simulation.settings().width;
+        let height = simulation.settings().height;
+        let scale = self.settings.food_noise_scale.max(f64::EPSILON);
+
+        // Only open cells away from the nests are candidates for food
+        let densities = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|(x, y)| !simulation.is_wall(*x, *y))
+            .filter(|(x, y)| {
+                nests.iter().all(|(nx, ny, _)| {
+                    distance((*x, *y), (*nx, *ny)) >= self.settings.food_nest_clearance
+                })
+            })
+            .map(|(x, y)| {
+                let value = noise.get([x as f64 / scale, y as f64 / scale]);
+                (x, y, ((value + 1.0) * 0.5) as f32)
+            })
+            .collect::<Vec<_>>();
+
+        // Pick the threshold so that roughly `food_coverage` of the candidates end up with food
+        let mut sorted = densities.iter().map(|(_, _, d)| *d).collect::<Vec<_>>();
+        sorted.sort_by(|a, b| b.total_cmp(a));
+        let coverage = self.settings.food_coverage.clamp(0.0, 1.0);
+        let Some(threshold) = sorted
+            .get(((sorted.len() as f32 * coverage) as usize).min(sorted.len().saturating_sub(1)))
+            .copied()
+            .filter(|_| coverage > 0.0)
+        else {
+            return;
+        };
+        let peak = sorted.first().copied().unwrap_or(1.0);
+
+        for (x, y, density) in densities {
+            if density < threshold {
+                continue;
+            }
+
+            let strength = (density - threshold) / (peak - threshold).max(f32::EPSILON);
+            let amount = (strength * self.settings.max_food_amount as f32).ceil() as u8;
+            simulation.spawn_food(x, y, amount.max(1));
+        }
+    }
+}
+
+fn distance(a: (u16, u16), b: (u16, u16)) -> f32 {
+    let dx = a.0 as f32 - b.0 as f32;
+    let dy = a.1 as f32 - b.1 as f32;
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::fixture::world_bytes;
+
+    fn world(seed: u64) -> Simulation {
+        let settings = WorldGenSettings {
+            seed,
+            caves: Some(CaveSettings::default()),
+            ..Default::default()
+        };
+        let simulation_settings = SimulationSettings {
+            width: 120,
+            height: 80,
+            ..Default::default()
+        };
+        WorldGenerator::new(settings).generate(simulation_settings)
+    }
+
+    #[test]
+    fn same_seed_generates_and_runs_identically() {
+        let mut a = world(7);
+        let mut b = world(7);
+        assert_eq!(a.settings().seed, 7);
+        assert_eq!(world_bytes(&a), world_bytes(&b));
+
+        for _ in 0..20 {
+            a.step();
+            b.step();
+        }
+        assert_eq!(world_bytes(&a), world_bytes(&b));
+    }
+
+    #[test]
+    fn different_seeds_generate_different_worlds() {
+        assert_ne!(world_bytes(&world(1)), world_bytes(&world(2)));
+    }
+
+    #[test]
+    fn full_food_coverage_does_not_panic() {
+        let settings = WorldGenSettings {
+            food_coverage: 1.0,
+            food_nest_clearance: 0.0,
+            ..Default::default()
+        };
+        let simulation_settings = SimulationSettings {
+            width: 40,
+            height: 30,
+            ..Default::default()
+        };
+        let simulation = WorldGenerator::new(settings).generate(simulation_settings);
+        let cells = simulation.snapshot().cells;
+        assert!(cells.iter().all(|c| c.food > 0 || c.flags.has_home()));
+    }
+}